@@ -0,0 +1,132 @@
+//! Checkpoint packing for the Monopoly engine, bit for bit the same as
+//! `MonopolyEngine.packForCheckpoint` / `fromCheckpoint` in `packages/engine`.
+//!
+//! `unpack` is strict: a value with bits set outside the layout is rejected, so anything that
+//! decodes also packs back to the same value.
+
+#![no_std]
+
+pub const BOARD_SIZE: u8 = 40;
+pub const NUM_PROPERTIES: usize = 28;
+pub const MAX_HOUSES: u8 = 4;
+
+/// Seats the engine plays. Player indices are 2 bits and owners 3 bits with `UNOWNED` = 7, so
+/// the layout has no room for more.
+pub const NUM_PLAYERS: usize = 4;
+
+/// Owner index the engine writes for a property nobody owns.
+pub const UNOWNED: u8 = 7;
+
+pub const PLAYER_BITS: u32 = 30;
+pub const PROPERTY_BITS: u32 = 4;
+pub const META_BITS: u32 = 37;
+
+// ========== PLAYERS ==========
+
+/// One 64-bit player slot: position(6) + cash(20) + alive(1) + inJail(1) + jailTurns(2).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CheckpointPlayer {
+    pub position: u8,
+    pub cash: u32,
+    pub alive: bool,
+    pub in_jail: bool,
+    pub jail_turns: u8,
+}
+
+impl CheckpointPlayer {
+    pub fn pack(&self) -> u64 {
+        (self.position as u64 & 0x3F)
+            | (self.cash as u64 & 0xF_FFFF) << 6
+            | (self.alive as u64) << 26
+            | (self.in_jail as u64) << 27
+            | (self.jail_turns as u64 & 0x3) << 28
+    }
+
+    pub fn unpack(slot: u64) -> Option<Self> {
+        if slot >> PLAYER_BITS != 0 {
+            return None;
+        }
+        Some(Self {
+            position: (slot & 0x3F) as u8,
+            cash: ((slot >> 6) & 0xF_FFFF) as u32,
+            alive: (slot >> 26) & 1 == 1,
+            in_jail: (slot >> 27) & 1 == 1,
+            jail_turns: ((slot >> 28) & 0x3) as u8,
+        })
+    }
+}
+
+// ========== PROPERTIES ==========
+
+/// One 4-bit property slot: owner(3, `UNOWNED` = 7) + mortgaged(1).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CheckpointProperty {
+    pub owner: Option<u8>,
+    pub mortgaged: bool,
+}
+
+impl CheckpointProperty {
+    pub fn pack(&self) -> u8 {
+        (self.owner.unwrap_or(UNOWNED) & 0x7) | (self.mortgaged as u8) << 3
+    }
+
+    pub fn unpack(slot: u8) -> Option<Self> {
+        if slot >> PROPERTY_BITS != 0 {
+            return None;
+        }
+        let owner = slot & 0x7;
+        Some(Self {
+            owner: (owner != UNOWNED).then_some(owner),
+            mortgaged: slot & 0x8 != 0,
+        })
+    }
+}
+
+pub fn pack_properties(properties: &[CheckpointProperty; NUM_PROPERTIES]) -> u128 {
+    properties.iter().enumerate().fold(0, |packed, (i, p)| {
+        packed | (p.pack() as u128) << (i as u32 * PROPERTY_BITS)
+    })
+}
+
+pub fn unpack_properties(packed: u128) -> Option<[CheckpointProperty; NUM_PROPERTIES]> {
+    if packed >> (NUM_PROPERTIES as u32 * PROPERTY_BITS) != 0 {
+        return None;
+    }
+    let mut properties = [CheckpointProperty::default(); NUM_PROPERTIES];
+    for (i, p) in properties.iter_mut().enumerate() {
+        *p = CheckpointProperty::unpack((packed >> (i as u32 * PROPERTY_BITS)) as u8 & 0xF)?;
+    }
+    Some(properties)
+}
+
+// ========== META ==========
+
+/// currentPlayer(2) + turn(16) + round(16) + aliveCount(3).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CheckpointMeta {
+    pub current_player: u8,
+    pub turn: u16,
+    pub round: u16,
+    pub alive_count: u8,
+}
+
+impl CheckpointMeta {
+    pub fn pack(&self) -> u128 {
+        (self.current_player as u128 & 0x3)
+            | (self.turn as u128) << 2
+            | (self.round as u128) << 18
+            | (self.alive_count as u128 & 0x7) << 34
+    }
+
+    pub fn unpack(packed: u128) -> Option<Self> {
+        if packed >> META_BITS != 0 {
+            return None;
+        }
+        Some(Self {
+            current_player: (packed & 0x3) as u8,
+            turn: (packed >> 2) as u16,
+            round: (packed >> 18) as u16,
+            alive_count: ((packed >> 34) & 0x7) as u8,
+        })
+    }
+}
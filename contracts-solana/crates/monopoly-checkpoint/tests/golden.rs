@@ -0,0 +1,117 @@
+//! Golden vectors shared with the TypeScript engine tests (`packages/engine/__tests__`).
+
+use monopoly_checkpoint::*;
+use serde_json::Value;
+
+const VECTORS: &str = include_str!("../vectors/checkpoint.json");
+
+fn vectors() -> Vec<Value> {
+    serde_json::from_str::<Value>(VECTORS)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+fn hex(v: &Value) -> u128 {
+    u128::from_str_radix(v.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+}
+
+/// The 256-bit `playersPacked` split into its 64-bit slots.
+fn player_slots(v: &Value) -> [u64; 4] {
+    let digits = format!("{:0>64}", v.as_str().unwrap().trim_start_matches("0x"));
+    let mut slots = [0u64; 4];
+    for (i, slot) in slots.iter_mut().enumerate() {
+        let end = 64 - i * 16;
+        *slot = u64::from_str_radix(&digits[end - 16..end], 16).unwrap();
+    }
+    slots
+}
+
+fn player(v: &Value) -> CheckpointPlayer {
+    CheckpointPlayer {
+        position: v["position"].as_u64().unwrap() as u8,
+        cash: v["cash"].as_u64().unwrap() as u32,
+        alive: v["alive"].as_bool().unwrap(),
+        in_jail: v["inJail"].as_bool().unwrap(),
+        jail_turns: v["jailTurns"].as_u64().unwrap() as u8,
+    }
+}
+
+fn properties(v: &Value) -> [CheckpointProperty; NUM_PROPERTIES] {
+    let owners = v["owners"].as_array().unwrap();
+    let mortgaged = v["mortgaged"].as_array().unwrap();
+    let mut properties = [CheckpointProperty::default(); NUM_PROPERTIES];
+    for (i, p) in properties.iter_mut().enumerate() {
+        let owner = owners[i].as_i64().unwrap();
+        p.owner = (owner >= 0).then_some(owner as u8);
+        p.mortgaged = mortgaged[i].as_bool().unwrap();
+    }
+    properties
+}
+
+fn meta(v: &Value) -> CheckpointMeta {
+    CheckpointMeta {
+        current_player: v["currentPlayerIndex"].as_u64().unwrap() as u8,
+        turn: v["currentTurn"].as_u64().unwrap() as u16,
+        round: v["currentRound"].as_u64().unwrap() as u16,
+        alive_count: v["aliveCount"].as_u64().unwrap() as u8,
+    }
+}
+
+#[test]
+fn packs_like_the_engine() {
+    for v in vectors() {
+        let name = v["name"].as_str().unwrap();
+        let slots = player_slots(&v["playersPacked"]);
+        for (i, p) in v["players"].as_array().unwrap().iter().enumerate() {
+            assert_eq!(player(p).pack(), slots[i], "{name}: player {i}");
+        }
+        assert_eq!(
+            pack_properties(&properties(&v)),
+            hex(&v["propertiesPacked"]),
+            "{name}: properties"
+        );
+        assert_eq!(
+            meta(&v["meta"]).pack(),
+            hex(&v["metaPacked"]),
+            "{name}: meta"
+        );
+    }
+}
+
+#[test]
+fn unpacks_like_the_engine() {
+    for v in vectors() {
+        let name = v["name"].as_str().unwrap();
+        let slots = player_slots(&v["playersPacked"]);
+        for (i, p) in v["players"].as_array().unwrap().iter().enumerate() {
+            assert_eq!(
+                CheckpointPlayer::unpack(slots[i]),
+                Some(player(p)),
+                "{name}: player {i}"
+            );
+        }
+        assert_eq!(
+            unpack_properties(hex(&v["propertiesPacked"])),
+            Some(properties(&v)),
+            "{name}: properties"
+        );
+        assert_eq!(
+            CheckpointMeta::unpack(hex(&v["metaPacked"])),
+            Some(meta(&v["meta"])),
+            "{name}: meta"
+        );
+    }
+}
+
+#[test]
+fn rejects_bits_outside_the_layout() {
+    assert_eq!(CheckpointPlayer::unpack(1 << PLAYER_BITS), None);
+    assert_eq!(CheckpointProperty::unpack(1 << PROPERTY_BITS), None);
+    assert_eq!(
+        unpack_properties(1 << (NUM_PROPERTIES as u32 * PROPERTY_BITS)),
+        None
+    );
+    assert_eq!(CheckpointMeta::unpack(1 << META_BITS), None);
+}
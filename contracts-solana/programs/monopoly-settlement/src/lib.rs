@@ -1,22 +1,55 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak;
 use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::{
+    self, Burn, CloseAccount, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked,
+};
+use monopoly_checkpoint::{
+    unpack_properties, CheckpointMeta, CheckpointPlayer, NUM_PLAYERS as CHECKPOINT_SEATS,
+};
+pub use monopoly_checkpoint::{BOARD_SIZE, MAX_HOUSES, NUM_PROPERTIES};
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
-pub const ENTRY_FEE: u64 = 10_000_000; // 0.01 SOL
-pub const NUM_PLAYERS: usize = 4;
-pub const WINNER_BPS: u64 = 8000;
-pub const PLATFORM_BPS: u64 = 2000;
-pub const REVEAL_TIMEOUT: i64 = 120;
-pub const DEPOSIT_TIMEOUT: i64 = 600;
-pub const GAME_TIMEOUT: i64 = 86400;
-pub const MAX_OPEN_GAMES: usize = 20;
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const DEFAULT_PLATFORM_FEE_BPS: u16 = 2000;
+pub const MAX_PLATFORM_FEE_BPS: u16 = 3000;
+pub const DEFAULT_REVEAL_TIMEOUT: i64 = 120;
+pub const DEFAULT_DEPOSIT_TIMEOUT: i64 = 600;
+pub const DEFAULT_GAME_TIMEOUT: i64 = 86400;
+pub const DEFAULT_CHALLENGE_PERIOD: i64 = 3600;
+/// Upper bound for any timeout, so deadlines computed from the clock cannot overflow.
+pub const MAX_TIMEOUT: i64 = 365 * 86400;
+pub const LOBBY_PAGE_SIZE: usize = 32;
+pub const CLAW_DECIMALS: u8 = 6;
+pub const STARTING_CLAW: u64 = 1_000 * 10u64.pow(CLAW_DECIMALS as u32); // 1000 CLAW per player
+pub const CHECKPOINT_VERSION: u8 = 3;
+/// Layout versions written into `version`. Accounts from before versioning read as 0.
+pub const PLATFORM_VERSION: u8 = 2;
+pub const GAME_VERSION: u8 = 4;
+pub const LOBBY_VERSION: u8 = 1;
+pub const TREASURY_VERSION: u8 = 1;
+/// How commit hashes are formed, recorded per game in `GameState.commit_scheme`.
+/// 0: `keccak(secret)`, games created before schemes were versioned.
+/// 1: `keccak(game_id (u64 LE) ‖ player ‖ secret)`, so a commit cannot be replayed in another
+/// game or seat.
+pub const COMMIT_SCHEME: u8 = 1;
+pub const CHECKPOINT_HISTORY: usize = 8;
+pub const MAX_GM_COMMITTEE: usize = 7;
+/// `PlatformConfig.paused` scopes. Refunds and payouts are never paused.
+pub const PAUSE_CREATE: u8 = 1 << 0;
+pub const PAUSE_DEPOSIT: u8 = 1 << 1;
+pub const PAUSE_SETTLE: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_CREATE | PAUSE_DEPOSIT | PAUSE_SETTLE;
 
 // ========== STATE ==========
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
 pub enum GameStatus {
+    #[default]
     Pending,
     Open,
     Depositing,
@@ -24,11 +57,73 @@ pub enum GameStatus {
     Started,
     Settled,
     Voided,
+    PendingSettlement,
+    Disputed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Timeouts {
+    pub reveal: i64,
+    pub deposit: i64,
+    pub game: i64,
+    /// How long after `settle_game` a seated player can dispute the result.
+    pub challenge: i64,
+}
+
+impl Timeouts {
+    pub const SIZE: usize = 8 + 8 + 8 + 8;
+
+    pub const DEFAULT: Timeouts = Timeouts {
+        reveal: DEFAULT_REVEAL_TIMEOUT,
+        deposit: DEFAULT_DEPOSIT_TIMEOUT,
+        game: DEFAULT_GAME_TIMEOUT,
+        challenge: DEFAULT_CHALLENGE_PERIOD,
+    };
+
+    pub const MAX: Timeouts = Timeouts {
+        reveal: MAX_TIMEOUT,
+        deposit: MAX_TIMEOUT,
+        game: MAX_TIMEOUT,
+        challenge: MAX_TIMEOUT,
+    };
+
+    pub fn is_within(&self, min: &Timeouts, max: &Timeouts) -> bool {
+        (min.reveal..=max.reveal).contains(&self.reveal)
+            && (min.deposit..=max.deposit).contains(&self.deposit)
+            && (min.game..=max.game).contains(&self.game)
+            && (min.challenge..=max.challenge).contains(&self.challenge)
+    }
+}
+
+/// How the owner or arbiter settles a disputed result.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum DisputeResolution {
+    /// The GM's ranking stands.
+    Confirm,
+    /// Replace the GM's ranking.
+    Overturn { ranking: Vec<Pubkey> },
+    /// Void the game and refund every depositor.
+    Void,
 }
 
-impl Default for GameStatus {
-    fn default() -> Self {
-        GameStatus::Pending
+/// Two dice as the engine's `DiceDeriver` rolls them for a turn.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiceRoll {
+    pub d1: u8,
+    pub d2: u8,
+}
+
+impl DiceRoll {
+    /// `keccak256(seed ‖ uint256(turn))`; the first two hash bytes, big-endian, give both dice.
+    pub fn derive(seed: &[u8; 32], turn: u64) -> Self {
+        let mut turn_word = [0u8; 32];
+        turn_word[24..].copy_from_slice(&turn.to_be_bytes());
+        let hash = keccak::hashv(&[seed, &turn_word]).to_bytes();
+        let n = u16::from_be_bytes([hash[0], hash[1]]);
+        DiceRoll {
+            d1: (n % 6) as u8 + 1,
+            d2: ((n / 6) % 6) as u8 + 1,
+        }
     }
 }
 
@@ -38,22 +133,82 @@ pub struct PlatformConfig {
     pub gm_signer: Pubkey,
     pub platform_fee_addr: Pubkey,
     pub game_count: u64,
-    pub open_game_ids: [u64; MAX_OPEN_GAMES],
-    pub open_game_count: u8,
+    pub open_game_count: u32,
+    pub lobby_page_count: u32,
     pub bump: u8,
+    pub default_timeouts: Timeouts,
+    pub min_timeouts: Timeouts,
+    pub max_timeouts: Timeouts,
+    pub platform_fee_bps: u16,
+    pub claw_mint: Pubkey,
+    /// Can resolve disputed settlements alongside the owner. Default key when unset.
+    pub arbiter: Pubkey,
+    /// Keys that must co-sign `settle_game`, `gm_threshold` of them. Empty leaves the GM signer
+    /// to settle alone.
+    pub gm_committee: Vec<Pubkey>,
+    pub gm_threshold: u8,
+    /// Proposed by `propose_owner`; becomes the owner once it calls `accept_owner`.
+    pub pending_owner: Pubkey,
+    /// Can pause scopes during an incident; only the owner can lift a pause.
+    pub guardian: Pubkey,
+    pub paused: u8,
+    pub version: u8,
+    /// Share of a non-revealer's forfeited deposit that goes to the treasury; the rest is split
+    /// between the players who revealed.
+    pub forfeit_treasury_bps: u16,
 }
 
 impl PlatformConfig {
-    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + (MAX_OPEN_GAMES * 8) + 1 + 1;
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + 4 + 4 + 1 + (3 * Timeouts::SIZE) + 2 + 32 + 32
+        + (4 + MAX_GM_COMMITTEE * 32) + 1 + 32 + 32 + 1 + 1 + 2;
+
+    pub fn is_paused(&self, scope: u8) -> bool {
+        self.paused & scope != 0
+    }
+
+    /// Committee members that signed: the GM itself, or co-signers in `cosigners`.
+    pub fn committee_approvals(&self, gm: &Pubkey, cosigners: &[AccountInfo]) -> usize {
+        self.gm_committee
+            .iter()
+            .filter(|member| {
+                *member == gm || cosigners.iter().any(|a| a.is_signer && a.key == *member)
+            })
+            .count()
+    }
+}
+
+/// The platform's rake. SOL fees are held as lamports on this account; token fees sit in one
+/// `[b"treasury", mint]` token account per mint, each its own authority.
+#[account]
+pub struct Treasury {
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl Treasury {
+    pub const SIZE: usize = 8 + 1 + 1;
+}
+
+/// One page of the open-game lobby. Clients enumerate pages `0..lobby_page_count`.
+#[account]
+pub struct LobbyPage {
+    pub page: u32,
+    pub game_ids: Vec<u64>,
+    pub bump: u8,
+    pub version: u8,
+}
+
+impl LobbyPage {
+    pub const SIZE: usize = 8 + 4 + (4 + LOBBY_PAGE_SIZE * 8) + 1 + 1;
 }
 
 #[account]
 pub struct GameState {
     pub game_id: u64,
     pub status: GameStatus,
-    pub players: [Pubkey; NUM_PLAYERS],
-    pub commit_hashes: [[u8; 32]; NUM_PLAYERS],
-    pub revealed_secrets: [[u8; 32]; NUM_PLAYERS],
+    pub players: Vec<Pubkey>,
+    pub commit_hashes: Vec<[u8; 32]>,
+    pub revealed_secrets: Vec<[u8; 32]>,
     pub deposit_count: u8,
     pub reveal_count: u8,
     pub dice_seed: [u8; 32],
@@ -62,17 +217,229 @@ pub struct GameState {
     pub reveal_deadline: i64,
     pub created_at: i64,
     pub started_at: i64,
-    pub winner_paid: bool,
+    /// Per seat: the payout was claimed on a settled game, or the refund on a voided one
+    pub paid: Vec<bool>,
     pub bump: u8,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub min_players: u8,
+    pub timeouts: Timeouts,
+    pub deposit_deadline: i64,
+    pub game_deadline: i64,
+    pub platform_fee_bps: u16,
+    pub payout_bps: Vec<u16>,
+    pub ranking: Vec<Pubkey>,
+    pub platform_paid: bool,
+    pub mint: Pubkey,
+    pub claw_mint: Pubkey,
+    pub lobby_page: u32,
+    pub rent_payer: Pubkey,
+    pub claw_accounts_open: u8,
+    pub challenge_deadline: i64,
+    pub disputed_by: Pubkey,
+    pub version: u8,
+    pub forfeit_treasury_bps: u16,
+    /// Depositors who failed to reveal and lost their entry fee when the game was voided
+    pub forfeited_count: u8,
+    /// What each revealer gets on top of their refund from the forfeited deposits
+    pub forfeit_share: u64,
+    pub commit_scheme: u8,
+    /// Per seat: bump of the depositor's CLAW account, recorded at deposit. Empty on games
+    /// migrated from before it, whose CLAW addresses are derived instead.
+    pub claw_bumps: Vec<u8>,
 }
 
 impl GameState {
-    pub const SIZE: usize = 8 + 8 + 1 + (NUM_PLAYERS * 32) + (NUM_PLAYERS * 32)
-        + (NUM_PLAYERS * 32) + 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1;
+    pub fn space(max_players: usize) -> usize {
+        8 + 8 + 1 + (4 + max_players * 32) + (4 + max_players * 32)
+            + (4 + max_players * 32) + 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + (4 + max_players)
+            + 1 + 8 + 1 + 1 + Timeouts::SIZE + 8 + 8 + 2 + (4 + max_players * 2)
+            + (4 + max_players * 32) + 1 + 32 + 32 + 4 + 32 + 1 + 8 + 32 + 1 + 2 + 1 + 8 + 1
+            + (4 + max_players)
+    }
+
+    /// Seat count, read before an old account is grown to `space`: fixed in the baseline
+    /// layout, otherwise the `players` length prefix, which sits at the same offset in every
+    /// later layout.
+    pub fn seats(data: &[u8]) -> Result<usize> {
+        if data.len() == GameStateV1::SIZE {
+            return Ok(GameStateV1::SEATS);
+        }
+        let prefix = data
+            .get(8 + 8 + 1..8 + 8 + 1 + 4)
+            .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+        Ok(u32::from_le_bytes(prefix.try_into().unwrap()) as usize)
+    }
+
+    /// Token games hold the pot in the game vault; SOL games hold it as lamports on this account.
+    pub fn is_token_game(&self) -> bool {
+        self.mint != Pubkey::default()
+    }
+
+    /// Games created after the CLAW mint was set up give each player in-game CLAW.
+    pub fn has_claw(&self) -> bool {
+        self.claw_mint != Pubkey::default()
+    }
+
+    /// True once nothing is left to pay out: every podium place and the platform share on a
+    /// settled game, or every depositor's refund on a voided one.
+    pub fn payouts_complete(&self) -> bool {
+        let seat_paid = |p: &Pubkey| {
+            self.players
+                .iter()
+                .position(|s| s == p)
+                .is_some_and(|seat| self.paid[seat])
+        };
+        match self.status {
+            GameStatus::Settled => {
+                self.platform_paid
+                    && self.ranking[..self.payout_bps.len()].iter().all(seat_paid)
+            }
+            GameStatus::Voided => self
+                .players
+                .iter()
+                .enumerate()
+                .filter(|(seat, p)| has_deposit(self, p) && !self.is_forfeited(*seat))
+                .all(|(_, p)| seat_paid(p)),
+            _ => false,
+        }
+    }
+
+    /// A depositor who never revealed in a game voided for it, with no refund to claim.
+    pub fn is_forfeited(&self, seat: usize) -> bool {
+        self.forfeited_count > 0 && self.revealed_secrets[seat] == [0u8; 32]
+    }
+
+    pub fn total_pot(&self) -> u64 {
+        self.entry_fee * self.deposit_count as u64
+    }
+
+    pub fn platform_share(&self) -> u64 {
+        let total_pot = self.total_pot();
+        total_pot - total_pot * (BPS_DENOMINATOR - self.platform_fee_bps as u64) / BPS_DENOMINATOR
+    }
+
+    /// Share of the prize pool for a finishing place. Rounding dust goes to first place.
+    pub fn place_share(&self, place: usize) -> u64 {
+        let pool = self.total_pot() - self.platform_share();
+        let share = |bps: &u16| pool * *bps as u64 / BPS_DENOMINATOR;
+        if place == 0 {
+            let others: u64 = self.payout_bps.iter().skip(1).map(share).sum();
+            pool - others
+        } else {
+            share(&self.payout_bps[place])
+        }
+    }
+}
+
+/// Engine state after a round. `players_packed` holds one 64-bit engine slot per seat and
+/// `houses` the building count per property. `version` is the layout the entry was written with:
+/// entries carried over from a v1 account only have the first two player slots and no houses.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct CheckpointEntry {
+    pub round: u64,
+    pub version: u8,
+    pub players_packed: [u64; MAX_PLAYERS],
+    pub properties_packed: u128,
+    pub meta_packed: u128,
+    pub houses: [u8; NUM_PROPERTIES],
+}
+
+impl CheckpointEntry {
+    pub const SIZE: usize = 8 + 1 + (MAX_PLAYERS * 8) + 16 + 16 + NUM_PROPERTIES;
 }
 
+/// Ring buffer of the last `CHECKPOINT_HISTORY` checkpoints of a game. Rounds strictly increase,
+/// so walking back from `latest()` goes back through the game.
 #[account]
 pub struct GameCheckpoint {
+    pub game_id: u64,
+    /// Checkpoints written so far, including ones already overwritten in `history`
+    pub count: u64,
+    pub history: [CheckpointEntry; CHECKPOINT_HISTORY],
+    pub bump: u8,
+    pub payer: Pubkey,
+}
+
+impl GameCheckpoint {
+    pub const SIZE: usize = 8 + 8 + 8 + (CHECKPOINT_HISTORY * CheckpointEntry::SIZE) + 1 + 32;
+
+    pub fn latest(&self) -> Option<&CheckpointEntry> {
+        self.recent().next()
+    }
+
+    /// Retained checkpoints, newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &CheckpointEntry> {
+        let retained = self.count.min(CHECKPOINT_HISTORY as u64);
+        (0..retained).map(move |back| {
+            &self.history[((self.count - 1 - back) % CHECKPOINT_HISTORY as u64) as usize]
+        })
+    }
+
+    pub fn push(&mut self, entry: CheckpointEntry) {
+        self.history[(self.count % CHECKPOINT_HISTORY as u64) as usize] = entry;
+        self.count += 1;
+    }
+
+    /// Decodes a checkpoint in any layout. Single-checkpoint (v1, v2) accounts come back as a
+    /// history holding that one entry.
+    pub fn unpack(data: &[u8]) -> Result<Self> {
+        let (game_id, entry, bump, payer) = match data.len() {
+            GameCheckpointV1::SIZE => {
+                let v1 = GameCheckpointV1::deserialize(&mut legacy_body::<GameCheckpoint>(data)?)?;
+                let mut players_packed = [0u64; MAX_PLAYERS];
+                players_packed[0] = v1.players_packed as u64;
+                players_packed[1] = (v1.players_packed >> 64) as u64;
+                let entry = CheckpointEntry {
+                    round: v1.round,
+                    version: 1,
+                    players_packed,
+                    properties_packed: v1.properties_packed,
+                    meta_packed: v1.meta_packed,
+                    houses: [0u8; NUM_PROPERTIES],
+                };
+                // v1 did not record who paid for it; migrate_checkpoint fills that in
+                (v1.game_id, entry, v1.bump, Pubkey::default())
+            }
+            GameCheckpointV2::SIZE => {
+                let v2 = GameCheckpointV2::deserialize(&mut legacy_body::<GameCheckpoint>(data)?)?;
+                let entry = CheckpointEntry {
+                    round: v2.round,
+                    version: v2.version,
+                    players_packed: v2.players_packed,
+                    properties_packed: v2.properties_packed,
+                    meta_packed: v2.meta_packed,
+                    houses: v2.houses,
+                };
+                (v2.game_id, entry, v2.bump, v2.payer)
+            }
+            _ => return Self::try_deserialize(&mut &data[..]),
+        };
+        let mut checkpoint = Self {
+            game_id,
+            count: 0,
+            history: [CheckpointEntry::default(); CHECKPOINT_HISTORY],
+            bump,
+            payer,
+        };
+        checkpoint.push(entry);
+        Ok(checkpoint)
+    }
+}
+
+/// Account body after the discriminator of `A`, which every layout of an account shares.
+fn legacy_body<A: Discriminator>(data: &[u8]) -> Result<&[u8]> {
+    require!(
+        data.len() >= 8 && data[..8] == A::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    Ok(&data[8..])
+}
+
+/// Checkpoint layout before versioning, without a recorded payer. Told apart from later layouts
+/// by its size.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GameCheckpointV1 {
     pub game_id: u64,
     pub round: u64,
     pub players_packed: u128,
@@ -81,10 +448,170 @@ pub struct GameCheckpoint {
     pub bump: u8,
 }
 
-impl GameCheckpoint {
+impl GameCheckpointV1 {
     pub const SIZE: usize = 8 + 8 + 8 + 16 + 16 + 16 + 1;
 }
 
+/// Single-checkpoint layout with a slot per seat and houses, overwritten every round.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GameCheckpointV2 {
+    pub game_id: u64,
+    pub round: u64,
+    pub version: u8,
+    pub players_packed: [u64; MAX_PLAYERS],
+    pub properties_packed: u128,
+    pub meta_packed: u128,
+    pub houses: [u8; NUM_PROPERTIES],
+    pub bump: u8,
+    pub payer: Pubkey,
+}
+
+impl GameCheckpointV2 {
+    pub const SIZE: usize =
+        8 + 8 + 8 + 1 + (MAX_PLAYERS * 8) + 16 + 16 + NUM_PROPERTIES + 1 + 32;
+}
+
+/// Platform config layout the program was first deployed with: open games kept in a fixed
+/// array instead of lobby pages, no timeouts, fees or version. Told apart from later layouts by
+/// its size.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlatformConfigV1 {
+    pub owner: Pubkey,
+    pub gm_signer: Pubkey,
+    pub platform_fee_addr: Pubkey,
+    pub game_count: u64,
+    pub open_game_ids: [u64; PlatformConfigV1::MAX_OPEN_GAMES],
+    pub open_game_count: u8,
+    pub bump: u8,
+}
+
+impl PlatformConfigV1 {
+    pub const MAX_OPEN_GAMES: usize = 20;
+    pub const SIZE: usize = 8 + 32 + 32 + 32 + 8 + (Self::MAX_OPEN_GAMES * 8) + 1 + 1;
+
+    pub fn open_games(&self) -> &[u64] {
+        &self.open_game_ids[..(self.open_game_count as usize).min(Self::MAX_OPEN_GAMES)]
+    }
+
+    /// The config in the current layout, at version 0. Open games move to lobby page 0; every
+    /// field the baseline did not have takes the value `initialize` gives it.
+    pub fn upgrade(self) -> PlatformConfig {
+        PlatformConfig {
+            owner: self.owner,
+            gm_signer: self.gm_signer,
+            platform_fee_addr: self.platform_fee_addr,
+            game_count: self.game_count,
+            open_game_count: self.open_games().len() as u32,
+            lobby_page_count: 1,
+            bump: self.bump,
+            default_timeouts: Timeouts::DEFAULT,
+            min_timeouts: Timeouts::DEFAULT,
+            max_timeouts: Timeouts::DEFAULT,
+            platform_fee_bps: DEFAULT_PLATFORM_FEE_BPS,
+            claw_mint: Pubkey::default(),
+            arbiter: Pubkey::default(),
+            gm_committee: Vec::new(),
+            gm_threshold: 0,
+            pending_owner: Pubkey::default(),
+            guardian: Pubkey::default(),
+            paused: 0,
+            version: 0,
+            forfeit_treasury_bps: 0,
+        }
+    }
+}
+
+/// Game layout the program was first deployed with: always four seats in fixed arrays, a fixed
+/// SOL entry fee and a winner-takes-all payout. Told apart from later layouts by its size.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct GameStateV1 {
+    pub game_id: u64,
+    pub status: GameStatus,
+    pub players: [Pubkey; GameStateV1::SEATS],
+    pub commit_hashes: [[u8; 32]; GameStateV1::SEATS],
+    pub revealed_secrets: [[u8; 32]; GameStateV1::SEATS],
+    pub deposit_count: u8,
+    pub reveal_count: u8,
+    pub dice_seed: [u8; 32],
+    pub winner: Pubkey,
+    pub game_log_hash: [u8; 32],
+    pub reveal_deadline: i64,
+    pub created_at: i64,
+    pub started_at: i64,
+    /// Set once the winner withdrew; the same call paid the platform share
+    pub winner_paid: bool,
+    pub bump: u8,
+}
+
+impl GameStateV1 {
+    pub const SEATS: usize = 4;
+    pub const ENTRY_FEE: u64 = 10_000_000;
+    pub const SIZE: usize = 8 + 8 + 1 + (Self::SEATS * 32) + (Self::SEATS * 32)
+        + (Self::SEATS * 32) + 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8 + 1 + 1;
+
+    /// The game in the current layout, at version 0. Deadlines are rebuilt from the baseline's
+    /// fixed timeouts, and the winner takes the whole prize pool after the default rake. A
+    /// voided baseline game refunded its depositors in the same call, so they are marked paid.
+    pub fn upgrade(self, rent_payer: Pubkey) -> GameState {
+        let settled = self.status == GameStatus::Settled;
+        let paid = self
+            .players
+            .iter()
+            .zip(self.commit_hashes.iter())
+            .map(|(player, commit)| match self.status {
+                GameStatus::Settled => self.winner_paid && *player == self.winner,
+                GameStatus::Voided => *commit != [0u8; 32],
+                _ => false,
+            })
+            .collect();
+        let timeouts = Timeouts::DEFAULT;
+        GameState {
+            game_id: self.game_id,
+            status: self.status,
+            players: self.players.to_vec(),
+            commit_hashes: self.commit_hashes.to_vec(),
+            revealed_secrets: self.revealed_secrets.to_vec(),
+            deposit_count: self.deposit_count,
+            reveal_count: self.reveal_count,
+            dice_seed: self.dice_seed,
+            winner: self.winner,
+            game_log_hash: self.game_log_hash,
+            reveal_deadline: self.reveal_deadline,
+            created_at: self.created_at,
+            started_at: self.started_at,
+            paid,
+            bump: self.bump,
+            entry_fee: Self::ENTRY_FEE,
+            max_players: Self::SEATS as u8,
+            min_players: Self::SEATS as u8,
+            timeouts,
+            deposit_deadline: self.created_at + timeouts.deposit,
+            game_deadline: if self.started_at > 0 {
+                self.started_at + timeouts.game
+            } else {
+                0
+            },
+            platform_fee_bps: DEFAULT_PLATFORM_FEE_BPS,
+            payout_bps: vec![BPS_DENOMINATOR as u16],
+            ranking: if settled { vec![self.winner] } else { Vec::new() },
+            platform_paid: settled && self.winner_paid,
+            mint: Pubkey::default(),
+            claw_mint: Pubkey::default(),
+            lobby_page: 0,
+            rent_payer,
+            claw_accounts_open: 0,
+            challenge_deadline: 0,
+            disputed_by: Pubkey::default(),
+            version: 0,
+            forfeit_treasury_bps: 0,
+            forfeited_count: 0,
+            forfeit_share: 0,
+            commit_scheme: 0,
+            claw_bumps: Vec::new(),
+        }
+    }
+}
+
 // ========== ERRORS ==========
 
 #[error_code]
@@ -111,20 +638,84 @@ pub enum SettlementError {
     AlreadyRevealed,
     #[msg("Secret hash does not match commit")]
     HashMismatch,
-    #[msg("Not the winner")]
+    #[msg("Player did not finish in a paid place")]
     NotWinner,
-    #[msg("Winner already paid")]
+    #[msg("Payout already claimed")]
     AlreadyPaid,
     #[msg("Cannot void: conditions not met")]
     CannotVoid,
     #[msg("Cannot cancel: conditions not met")]
     CannotCancel,
-    #[msg("Open game list is full")]
+    #[msg("Lobby page is full")]
     OpenGamesFull,
     #[msg("Winner is not a player in this game")]
     WinnerNotPlayer,
     #[msg("Cannot emergency void: conditions not met")]
     CannotEmergencyVoid,
+    #[msg("Player count is out of range")]
+    InvalidPlayerCount,
+    #[msg("Not enough players have deposited")]
+    NotEnoughPlayers,
+    #[msg("Timeouts are invalid or outside the allowed bounds")]
+    InvalidTimeouts,
+    #[msg("Platform fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("Payout table must cover at most min_players places and sum to 10000 bps")]
+    InvalidPayoutTable,
+    #[msg("Ranking must list every depositor exactly once")]
+    InvalidRanking,
+    #[msg("Token game requires the mint, vault, token program and token accounts")]
+    MissingTokenAccounts,
+    #[msg("Mint does not match the game mint")]
+    WrongMint,
+    #[msg("Vault did not receive the full entry fee")]
+    VaultShortfall,
+    #[msg("CLAW accounts are missing or do not match the depositing players in seat order")]
+    InvalidClawAccounts,
+    #[msg("Roster must list distinct, non-default player keys")]
+    InvalidRoster,
+    #[msg("Lobby page is missing or out of range")]
+    InvalidLobbyPage,
+    #[msg("Cannot close: payouts, refunds, CLAW or checkpoint still outstanding")]
+    CannotClose,
+    #[msg("Checkpoint account does not belong to this game")]
+    InvalidCheckpoint,
+    #[msg("Checkpoint round must be after the last checkpointed round")]
+    StaleCheckpoint,
+    #[msg("Checkpoint state does not decode to a valid engine state")]
+    InvalidCheckpointState,
+    #[msg("Checkpoint state cannot follow from the previous checkpoint")]
+    InvalidCheckpointTransition,
+    #[msg("Claimed dice roll does not match the dice seed")]
+    RollMismatch,
+    #[msg("Settlement is still in its challenge period")]
+    ChallengePeriodActive,
+    #[msg("Challenge period for this settlement has ended")]
+    ChallengePeriodOver,
+    #[msg("Only the owner or arbiter can resolve a dispute")]
+    NotArbiter,
+    #[msg("GM committee must list distinct keys and a threshold between 1 and its size")]
+    InvalidCommittee,
+    #[msg("Not enough GM committee members signed")]
+    ThresholdNotMet,
+    #[msg("Only the proposed owner can accept ownership")]
+    NotPendingOwner,
+    #[msg("This operation is paused")]
+    Paused,
+    #[msg("Only the owner can lift a pause, and only the owner or guardian can pause")]
+    NotGuardian,
+    #[msg("Forfeit split cannot send more than 100% to the treasury")]
+    InvalidForfeitSplit,
+    #[msg("Deposit was forfeited for not revealing")]
+    DepositForfeited,
+    #[msg("Secret cannot be all zeroes")]
+    EmptySecret,
+    #[msg("Checkpoints only cover games seating the engine's four players")]
+    CheckpointSeatsUnsupported,
+    #[msg("Forfeited deposits need the treasury account")]
+    MissingTreasury,
+    #[msg("Migrating the baseline platform needs lobby page 0")]
+    MissingLobbyPage,
 }
 
 // ========== PROGRAM ==========
@@ -143,58 +734,196 @@ pub mod monopoly_settlement {
         platform.gm_signer = gm_signer;
         platform.platform_fee_addr = platform_fee_addr;
         platform.game_count = 0;
-        platform.open_game_ids = [0u64; MAX_OPEN_GAMES];
         platform.open_game_count = 0;
+        platform.lobby_page_count = 0;
         platform.bump = ctx.bumps.platform;
+        platform.default_timeouts = Timeouts::DEFAULT;
+        platform.min_timeouts = Timeouts::DEFAULT;
+        platform.max_timeouts = Timeouts::DEFAULT;
+        platform.platform_fee_bps = DEFAULT_PLATFORM_FEE_BPS;
+        platform.claw_mint = Pubkey::default();
+        platform.arbiter = Pubkey::default();
+        platform.gm_committee = Vec::new();
+        platform.gm_threshold = 0;
+        platform.pending_owner = Pubkey::default();
+        platform.guardian = Pubkey::default();
+        platform.paused = 0;
+        platform.version = PLATFORM_VERSION;
+        platform.forfeit_treasury_bps = 0;
+        Ok(())
+    }
+
+    pub fn init_claw_mint(ctx: Context<InitClawMint>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        platform.claw_mint = ctx.accounts.claw_mint.key();
+        Ok(())
+    }
+
+    /// Creates the fee treasury. Games can only be finalized once it exists.
+    pub fn init_treasury(ctx: Context<InitTreasury>) -> Result<()> {
+        require!(
+            ctx.accounts.platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.bump = ctx.bumps.treasury;
+        treasury.version = TREASURY_VERSION;
         Ok(())
     }
 
-    pub fn create_open_game(ctx: Context<CreateOpenGame>) -> Result<()> {
+    pub fn create_open_game(
+        ctx: Context<CreateOpenGame>,
+        entry_fee: u64,
+        max_players: u8,
+        min_players: Option<u8>,
+        timeouts: Option<Timeouts>,
+        payout_bps: Vec<u16>,
+        page: u32,
+    ) -> Result<()> {
         let platform = &mut ctx.accounts.platform;
         require!(
             platform.gm_signer == ctx.accounts.gm.key(),
             SettlementError::NotGM
         );
+        require!(!platform.is_paused(PAUSE_CREATE), SettlementError::Paused);
+        require!(
+            page <= platform.lobby_page_count,
+            SettlementError::InvalidLobbyPage
+        );
         require!(
-            (platform.open_game_count as usize) < MAX_OPEN_GAMES,
+            ctx.accounts.lobby_page.game_ids.len() < LOBBY_PAGE_SIZE,
             SettlementError::OpenGamesFull
         );
+        let (min_players, timeouts) = validate_table(
+            platform,
+            entry_fee,
+            max_players,
+            min_players,
+            timeouts,
+            &payout_bps,
+        )?;
+        require!(
+            ctx.accounts.mint.is_some() == ctx.accounts.vault.is_some()
+                && ctx.accounts.mint.is_some() == ctx.accounts.treasury_vault.is_some(),
+            SettlementError::MissingTokenAccounts
+        );
+        let mint = ctx.accounts.mint.as_ref().map(|m| m.key()).unwrap_or_default();
+
+        let game_id = init_game(
+            &mut ctx.accounts.game,
+            platform,
+            ctx.bumps.game,
+            NewGame {
+                status: GameStatus::Open,
+                players: vec![Pubkey::default(); max_players as usize],
+                entry_fee,
+                min_players,
+                timeouts,
+                payout_bps,
+                mint,
+            },
+        )?;
 
-        let game_id = platform.game_count;
-        let clock = Clock::get()?;
+        ctx.accounts.game.lobby_page = page;
 
-        let game = &mut ctx.accounts.game;
-        game.game_id = game_id;
-        game.status = GameStatus::Open;
-        game.players = [Pubkey::default(); NUM_PLAYERS];
-        game.commit_hashes = [[0u8; 32]; NUM_PLAYERS];
-        game.revealed_secrets = [[0u8; 32]; NUM_PLAYERS];
-        game.deposit_count = 0;
-        game.reveal_count = 0;
-        game.dice_seed = [0u8; 32];
-        game.winner = Pubkey::default();
-        game.game_log_hash = [0u8; 32];
-        game.reveal_deadline = 0;
-        game.created_at = clock.unix_timestamp;
-        game.started_at = 0;
-        game.winner_paid = false;
-        game.bump = ctx.bumps.game;
-
-        let idx = platform.open_game_count as usize;
-        platform.open_game_ids[idx] = game_id;
+        let lobby = &mut ctx.accounts.lobby_page;
+        lobby.page = page;
+        lobby.bump = ctx.bumps.lobby_page;
+        lobby.version = LOBBY_VERSION;
+        lobby.game_ids.push(game_id);
+        if page == platform.lobby_page_count {
+            platform.lobby_page_count += 1;
+        }
         platform.open_game_count += 1;
-        platform.game_count += 1;
 
-        emit!(OpenGameCreated { game_id });
+        emit!(OpenGameCreated {
+            game_id,
+            entry_fee,
+            max_players,
+            mint,
+        });
+        Ok(())
+    }
+
+    /// Creates a game with a fixed roster. Only the seated players can deposit.
+    pub fn create_game(
+        ctx: Context<CreateGame>,
+        players: Vec<Pubkey>,
+        entry_fee: u64,
+        min_players: Option<u8>,
+        timeouts: Option<Timeouts>,
+        payout_bps: Vec<u16>,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.gm_signer == ctx.accounts.gm.key(),
+            SettlementError::NotGM
+        );
+        require!(!platform.is_paused(PAUSE_CREATE), SettlementError::Paused);
+        require!(
+            players.len() <= MAX_PLAYERS,
+            SettlementError::InvalidPlayerCount
+        );
+        for (i, p) in players.iter().enumerate() {
+            require!(
+                *p != Pubkey::default() && !players[..i].contains(p),
+                SettlementError::InvalidRoster
+            );
+        }
+        let max_players = players.len() as u8;
+        let (min_players, timeouts) = validate_table(
+            platform,
+            entry_fee,
+            max_players,
+            min_players,
+            timeouts,
+            &payout_bps,
+        )?;
+        require!(
+            ctx.accounts.mint.is_some() == ctx.accounts.vault.is_some()
+                && ctx.accounts.mint.is_some() == ctx.accounts.treasury_vault.is_some(),
+            SettlementError::MissingTokenAccounts
+        );
+        let mint = ctx.accounts.mint.as_ref().map(|m| m.key()).unwrap_or_default();
+
+        let game_id = init_game(
+            &mut ctx.accounts.game,
+            platform,
+            ctx.bumps.game,
+            NewGame {
+                status: GameStatus::Depositing,
+                players: players.clone(),
+                entry_fee,
+                min_players,
+                timeouts,
+                payout_bps,
+                mint,
+            },
+        )?;
+
+        emit!(GameCreated {
+            game_id,
+            players,
+            entry_fee,
+            mint,
+        });
         Ok(())
     }
 
     pub fn deposit_and_commit(
-        ctx: Context<DepositAndCommit>,
+        mut ctx: Context<DepositAndCommit>,
         secret_hash: [u8; 32],
     ) -> Result<()> {
+        require!(
+            !ctx.accounts.platform.is_paused(PAUSE_DEPOSIT),
+            SettlementError::Paused
+        );
         let player_key = ctx.accounts.player.key();
-        let (game_id, deposit_count) = {
+        let (game_id, deposit_count, entry_fee) = {
             let game = &mut ctx.accounts.game;
             require!(
                 game.status == GameStatus::Open || game.status == GameStatus::Depositing,
@@ -202,30 +931,22 @@ pub mod monopoly_settlement {
             );
             require!(secret_hash != [0u8; 32], SettlementError::EmptyCommitHash);
 
-            for i in 0..NUM_PLAYERS {
-                if game.players[i] == player_key && game.commit_hashes[i] != [0u8; 32] {
-                    return Err(SettlementError::AlreadyDeposited.into());
-                }
-            }
+            require!(
+                !has_deposit(game, &player_key),
+                SettlementError::AlreadyDeposited
+            );
+            require!(
+                !game.has_claw() || ctx.accounts.player_claw.is_some(),
+                SettlementError::InvalidClawAccounts
+            );
 
             let player_idx = if game.status == GameStatus::Open {
-                let mut idx = None;
-                for i in 0..NUM_PLAYERS {
-                    if game.players[i] == Pubkey::default() {
-                        idx = Some(i);
-                        break;
-                    }
-                }
-                idx.ok_or(SettlementError::NoEmptySlot)?
+                game.players
+                    .iter()
+                    .position(|p| *p == Pubkey::default())
+                    .ok_or(SettlementError::NoEmptySlot)?
             } else {
-                let mut idx = None;
-                for i in 0..NUM_PLAYERS {
-                    if game.players[i] == player_key {
-                        idx = Some(i);
-                        break;
-                    }
-                }
-                idx.ok_or(SettlementError::NotAPlayer)?
+                find_player_index(game, &player_key)?
             };
 
             if game.status == GameStatus::Open {
@@ -234,19 +955,58 @@ pub mod monopoly_settlement {
 
             game.commit_hashes[player_idx] = secret_hash;
             game.deposit_count += 1;
-            (game.game_id, game.deposit_count)
+            if game.has_claw() {
+                game.claw_accounts_open += 1;
+            }
+            if let (Some(bump), Some(slot)) =
+                (ctx.bumps.player_claw, game.claw_bumps.get_mut(player_idx))
+            {
+                *slot = bump;
+            }
+            (game.game_id, game.deposit_count, game.entry_fee)
         };
 
-        system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.player.to_account_info(),
-                    to: ctx.accounts.game.to_account_info(),
-                },
-            ),
-            ENTRY_FEE,
-        )?;
+        if ctx.accounts.game.is_token_game() {
+            let accounts = &mut ctx.accounts;
+            let (Some(source), Some(vault), Some(mint), Some(token_program)) = (
+                accounts.player_token_account.as_ref(),
+                accounts.vault.as_mut(),
+                accounts.mint.as_ref(),
+                accounts.token_program.as_ref(),
+            ) else {
+                return err!(SettlementError::MissingTokenAccounts);
+            };
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: source.to_account_info(),
+                        mint: mint.to_account_info(),
+                        to: vault.to_account_info(),
+                        authority: accounts.player.to_account_info(),
+                    },
+                ),
+                entry_fee,
+                mint.decimals,
+            )?;
+            // Mints that skim a fee on transfer would leave the pot short of what payouts assume
+            vault.reload()?;
+            require!(
+                vault.amount >= accounts.game.total_pot(),
+                SettlementError::VaultShortfall
+            );
+        } else {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.player.to_account_info(),
+                        to: ctx.accounts.game.to_account_info(),
+                    },
+                ),
+                entry_fee,
+            )?;
+        }
 
         emit!(DepositAndCommitEvent {
             game_id,
@@ -254,25 +1014,110 @@ pub mod monopoly_settlement {
             commit_hash: secret_hash,
         });
 
-        if deposit_count == NUM_PLAYERS as u8 {
+        if deposit_count == ctx.accounts.game.max_players {
+            let accounts = &mut ctx.accounts;
+            begin_reveal(
+                &mut accounts.game,
+                &mut accounts.platform,
+                accounts.lobby_page.as_deref_mut(),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Takes a depositor back out of an open game before it fills: refunds their entry fee,
+    /// frees the seat for someone else and closes their (still empty) CLAW account.
+    pub fn leave_game(ctx: Context<LeaveGame>) -> Result<()> {
+        let player_key = ctx.accounts.player.key();
+        let (game_id, entry_fee) = {
             let game = &mut ctx.accounts.game;
-            let clock = Clock::get()?;
-            game.status = GameStatus::Revealing;
-            game.reveal_deadline = clock.unix_timestamp + REVEAL_TIMEOUT;
+            require!(
+                game.status == GameStatus::Open,
+                SettlementError::InvalidGameStatus
+            );
+            let seat = find_player_index(game, &player_key)?;
+            require!(has_deposit(game, &player_key), SettlementError::NotAPlayer);
+            require!(
+                !game.has_claw()
+                    || (ctx.accounts.player_claw.is_some()
+                        && ctx.accounts.claw_token_program.is_some()),
+                SettlementError::InvalidClawAccounts
+            );
 
-            let platform = &mut ctx.accounts.platform;
-            remove_from_open_games(platform, game.game_id);
+            game.players[seat] = Pubkey::default();
+            game.commit_hashes[seat] = [0u8; 32];
+            game.deposit_count -= 1;
+            if game.has_claw() {
+                game.claw_accounts_open -= 1;
+            }
+            (game.game_id, game.entry_fee)
+        };
 
-            emit!(AllDeposited {
-                game_id: game.game_id,
-                reveal_deadline: game.reveal_deadline,
-            });
+        let accounts = &ctx.accounts;
+        let vault = vault_accounts(
+            &accounts.game,
+            &accounts.vault,
+            &accounts.mint,
+            &accounts.token_program,
+        )?;
+        let dest = match vault {
+            None => accounts.player.to_account_info(),
+            Some(_) => accounts
+                .player_token_account
+                .as_ref()
+                .ok_or(SettlementError::MissingTokenAccounts)?
+                .to_account_info(),
+        };
+        pay_out(&accounts.game, &vault, &dest, entry_fee)?;
+
+        if let (Some(player_claw), Some(claw_token_program)) =
+            (&accounts.player_claw, &accounts.claw_token_program)
+        {
+            let game_id = game_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[accounts.game.bump]];
+            token_interface::close_account(CpiContext::new_with_signer(
+                claw_token_program.to_account_info(),
+                CloseAccount {
+                    account: player_claw.to_account_info(),
+                    destination: accounts.player.to_account_info(),
+                    authority: accounts.game.to_account_info(),
+                },
+                &[seeds],
+            ))?;
         }
 
+        emit!(PlayerLeft {
+            game_id,
+            player: player_key,
+            amount: entry_fee,
+        });
+
         Ok(())
     }
 
-    pub fn reveal_seed(ctx: Context<RevealSeed>, secret: [u8; 32]) -> Result<()> {
+    pub fn start_reveal(ctx: Context<StartReveal>) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        require!(
+            game.status == GameStatus::Open || game.status == GameStatus::Depositing,
+            SettlementError::InvalidGameStatus
+        );
+        require!(
+            game.deposit_count >= game.min_players,
+            SettlementError::NotEnoughPlayers
+        );
+
+        begin_reveal(
+            game,
+            &mut ctx.accounts.platform,
+            ctx.accounts.lobby_page.as_deref_mut(),
+        )
+    }
+
+    pub fn reveal_seed<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealSeed<'info>>,
+        secret: [u8; 32],
+    ) -> Result<()> {
         let game = &mut ctx.accounts.game;
         require!(
             game.status == GameStatus::Revealing,
@@ -282,6 +1127,9 @@ pub mod monopoly_settlement {
         let player_key = ctx.accounts.player.key();
         let player_idx = find_player_index(game, &player_key)?;
 
+        // An all-zero secret is what an unrevealed seat holds, so it could be revealed again
+        // and would read as forfeited
+        require!(secret != [0u8; 32], SettlementError::EmptySecret);
         require!(
             game.commit_hashes[player_idx] != [0u8; 32],
             SettlementError::NoCommit
@@ -291,9 +1139,8 @@ pub mod monopoly_settlement {
             SettlementError::AlreadyRevealed
         );
 
-        let computed_hash = keccak::hash(&secret).to_bytes();
         require!(
-            computed_hash == game.commit_hashes[player_idx],
+            commitment(game, &player_key, &secret) == game.commit_hashes[player_idx],
             SettlementError::HashMismatch
         );
 
@@ -305,21 +1152,31 @@ pub mod monopoly_settlement {
             player: player_key,
         });
 
-        if game.reveal_count == NUM_PLAYERS as u8 {
+        if game.reveal_count == game.deposit_count {
             let mut seed = [0u8; 32];
-            for i in 0..NUM_PLAYERS {
-                for j in 0..32 {
-                    seed[j] ^= game.revealed_secrets[i][j];
+            for secret in game.revealed_secrets.iter() {
+                for (s, b) in seed.iter_mut().zip(secret.iter()) {
+                    *s ^= b;
                 }
             }
             game.dice_seed = seed;
             game.status = GameStatus::Started;
             game.started_at = Clock::get()?.unix_timestamp;
+            game.game_deadline = game.started_at + game.timeouts.game;
 
             emit!(GameStarted {
                 game_id: game.game_id,
                 dice_seed: seed,
             });
+
+            if game.has_claw() {
+                mint_starting_claw(
+                    game,
+                    &ctx.accounts.claw_mint,
+                    &ctx.accounts.claw_token_program,
+                    ctx.remaining_accounts,
+                )?;
+            }
         }
 
         Ok(())
@@ -328,24 +1185,41 @@ pub mod monopoly_settlement {
     pub fn write_checkpoint(
         ctx: Context<WriteCheckpoint>,
         round: u64,
-        players_packed: u128,
+        players_packed: [u64; MAX_PLAYERS],
         properties_packed: u128,
         meta_packed: u128,
+        houses: [u8; NUM_PROPERTIES],
     ) -> Result<()> {
         let game = &ctx.accounts.game;
         require!(
             game.status == GameStatus::Started,
             SettlementError::InvalidGameStatus
         );
+        // The engine always plays and packs exactly four seats
+        require!(
+            game.deposit_count as usize == CHECKPOINT_SEATS,
+            SettlementError::CheckpointSeatsUnsupported
+        );
 
         let checkpoint = &mut ctx.accounts.checkpoint;
+        let entry = CheckpointEntry {
+            round,
+            version: CHECKPOINT_VERSION,
+            players_packed,
+            properties_packed,
+            meta_packed,
+            houses,
+        };
+        let prev = checkpoint.latest();
+        if let Some(prev) = prev {
+            require!(round > prev.round, SettlementError::StaleCheckpoint);
+        }
+        validate_checkpoint(game, &entry, prev)?;
         checkpoint.game_id = game.game_id;
-        checkpoint.round = round;
-        checkpoint.players_packed = players_packed;
-        checkpoint.properties_packed = properties_packed;
-        checkpoint.meta_packed = meta_packed;
+        checkpoint.push(entry);
         if checkpoint.bump == 0 {
             checkpoint.bump = ctx.bumps.checkpoint;
+            checkpoint.payer = ctx.accounts.gm.key();
         }
 
         emit!(CheckpointWritten {
@@ -356,88 +1230,397 @@ pub mod monopoly_settlement {
         Ok(())
     }
 
-    pub fn settle_game(
-        ctx: Context<SettleGame>,
-        winner_pubkey: Pubkey,
-        game_log_hash: [u8; 32],
+    /// Returns the dice for `turn` through return data. Read-only; meant for simulation and CPI.
+    pub fn derive_dice(ctx: Context<ReadDice>, turn: u64) -> Result<DiceRoll> {
+        let game = &ctx.accounts.game;
+        require!(game.started_at > 0, SettlementError::InvalidGameStatus);
+        Ok(DiceRoll::derive(&game.dice_seed, turn))
+    }
+
+    /// Checks claimed rolls for consecutive turns starting at `first_turn` against the dice seed.
+    pub fn verify_rolls(
+        ctx: Context<ReadDice>,
+        first_turn: u64,
+        rolls: Vec<DiceRoll>,
     ) -> Result<()> {
-        let game = &mut ctx.accounts.game;
-        require!(
-            game.status == GameStatus::Started,
-            SettlementError::InvalidGameStatus
-        );
+        let game = &ctx.accounts.game;
+        require!(game.started_at > 0, SettlementError::InvalidGameStatus);
+        for (turn, roll) in (first_turn..).zip(rolls.iter()) {
+            if *roll != DiceRoll::derive(&game.dice_seed, turn) {
+                msg!("Roll for turn {} does not match the dice seed", turn);
+                return err!(SettlementError::RollMismatch);
+            }
+        }
+        Ok(())
+    }
+
+    /// Grows a single-checkpoint (v1, v2) account into the current history layout so
+    /// write_checkpoint and close_checkpoint can load it. The caller pays the extra rent; a v1
+    /// account's rent is returned to the game's rent payer on close. Current-layout checkpoints
+    /// are left untouched.
+    pub fn migrate_checkpoint(ctx: Context<MigrateCheckpoint>) -> Result<()> {
+        let info = ctx.accounts.checkpoint.to_account_info();
+        if info.data_len() == GameCheckpoint::SIZE {
+            return Ok(());
+        }
+        let mut checkpoint = GameCheckpoint::unpack(&info.try_borrow_data()?)?;
         require!(
-            is_player(game, &winner_pubkey),
-            SettlementError::WinnerNotPlayer
+            checkpoint.game_id == ctx.accounts.game.game_id,
+            SettlementError::InvalidCheckpoint
         );
+        // The GM wrote v1 checkpoints at its own expense, so their rent follows the game's
+        if checkpoint.payer == Pubkey::default() {
+            checkpoint.payer = ctx.accounts.game.rent_payer;
+        }
 
-        game.winner = winner_pubkey;
-        game.game_log_hash = game_log_hash;
-        game.status = GameStatus::Settled;
+        grow_account(
+            &info,
+            GameCheckpoint::SIZE,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+        checkpoint.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(CheckpointMigrated {
+            game_id: checkpoint.game_id,
+            round: checkpoint.latest().map_or(0, |c| c.round),
+        });
+
+        Ok(())
+    }
+
+    /// Upgrades the platform config to the current layout. Run first after deploying a program
+    /// that changes it; fields added since the account's version start zeroed. A config still
+    /// in the baseline layout also needs lobby page 0, created here for its open games.
+    pub fn migrate_platform(ctx: Context<MigratePlatform>) -> Result<()> {
+        let info = ctx.accounts.platform.to_account_info();
+        let baseline = if info.data_len() == PlatformConfigV1::SIZE {
+            let data = info.try_borrow_data()?;
+            Some(PlatformConfigV1::deserialize(
+                &mut legacy_body::<PlatformConfig>(&data)?,
+            )?)
+        } else {
+            None
+        };
+        grow_account(
+            &info,
+            PlatformConfig::SIZE,
+            &ctx.accounts.owner,
+            &ctx.accounts.system_program,
+        )?;
+        let mut platform = match baseline {
+            Some(v1) => {
+                let lobby = ctx
+                    .accounts
+                    .lobby_page
+                    .as_mut()
+                    .ok_or(SettlementError::MissingLobbyPage)?;
+                lobby.page = 0;
+                lobby.game_ids = v1.open_games().to_vec();
+                lobby.bump = ctx.bumps.lobby_page.unwrap_or_default();
+                lobby.version = LOBBY_VERSION;
+                v1.upgrade()
+            }
+            None => PlatformConfig::try_deserialize(&mut &info.try_borrow_data()?[..])?,
+        };
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        let from_version = platform.version;
+        platform.version = PLATFORM_VERSION;
+        platform.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: PLATFORM_VERSION,
+        });
+
+        Ok(())
+    }
+
+    /// Upgrades a game to the current layout. The owner pays any extra rent. Baseline games
+    /// were created at the GM's expense, so their rent goes back to the GM signer.
+    pub fn migrate_game(ctx: Context<MigrateGame>, _game_id: u64) -> Result<()> {
+        let info = ctx.accounts.game.to_account_info();
+        let seats = GameState::seats(&info.try_borrow_data()?)?;
+        let baseline = if info.data_len() == GameStateV1::SIZE {
+            let data = info.try_borrow_data()?;
+            Some(GameStateV1::deserialize(
+                &mut legacy_body::<GameState>(&data)?,
+            )?)
+        } else {
+            None
+        };
+        grow_account(
+            &info,
+            GameState::space(seats),
+            &ctx.accounts.owner,
+            &ctx.accounts.system_program,
+        )?;
+        let mut game = match baseline {
+            Some(v1) => v1.upgrade(ctx.accounts.platform.gm_signer),
+            None => GameState::try_deserialize(&mut &info.try_borrow_data()?[..])?,
+        };
+        let from_version = game.version;
+        game.version = GAME_VERSION;
+        game.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: GAME_VERSION,
+        });
+
+        Ok(())
+    }
+
+    /// Upgrades a lobby page to the current layout. The owner pays any extra rent.
+    pub fn migrate_lobby_page(ctx: Context<MigrateLobbyPage>, _page: u32) -> Result<()> {
+        let info = ctx.accounts.lobby_page.to_account_info();
+        grow_account(
+            &info,
+            LobbyPage::SIZE,
+            &ctx.accounts.owner,
+            &ctx.accounts.system_program,
+        )?;
+        let mut lobby = LobbyPage::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let from_version = lobby.version;
+        lobby.version = LOBBY_VERSION;
+        lobby.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: info.key(),
+            from_version,
+            to_version: LOBBY_VERSION,
+        });
+
+        Ok(())
+    }
+
+    /// Records the GM's result. Payouts open once the challenge period passes without a dispute.
+    /// With a GM committee set, committee co-signers are passed as signing remaining_accounts.
+    pub fn settle_game(
+        ctx: Context<SettleGame>,
+        ranking: Vec<Pubkey>,
+        game_log_hash: [u8; 32],
+    ) -> Result<()> {
+        let platform = &ctx.accounts.platform;
+        require!(!platform.is_paused(PAUSE_SETTLE), SettlementError::Paused);
+        require!(
+            platform.committee_approvals(&ctx.accounts.gm.key(), ctx.remaining_accounts)
+                >= platform.gm_threshold as usize,
+            SettlementError::ThresholdNotMet
+        );
+        let game = &mut ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(
+            game.status == GameStatus::Started,
+            SettlementError::InvalidGameStatus
+        );
+        validate_ranking(game, &ranking)?;
+
+        game.winner = ranking[0];
+        game.ranking = ranking.clone();
+        game.game_log_hash = game_log_hash;
+        game.status = GameStatus::PendingSettlement;
+        game.challenge_deadline = clock.unix_timestamp + game.timeouts.challenge;
 
         emit!(GameSettledEvent {
             game_id: game.game_id,
-            winner: winner_pubkey,
+            winner: game.winner,
+            ranking,
             game_log_hash,
+            challenge_deadline: game.challenge_deadline,
+        });
+
+        Ok(())
+    }
+
+    /// A seated player disputes the GM's result during the challenge period, freezing payouts
+    /// until the owner or arbiter resolves it.
+    pub fn dispute_settlement(ctx: Context<DisputeSettlement>) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        let clock = Clock::get()?;
+        require!(
+            game.status == GameStatus::PendingSettlement,
+            SettlementError::InvalidGameStatus
+        );
+        require!(
+            clock.unix_timestamp <= game.challenge_deadline,
+            SettlementError::ChallengePeriodOver
+        );
+        let player = ctx.accounts.player.key();
+        require!(has_deposit(game, &player), SettlementError::NotAPlayer);
+
+        game.status = GameStatus::Disputed;
+        game.disputed_by = player;
+
+        emit!(SettlementDisputed {
+            game_id: game.game_id,
+            player,
+        });
+
+        Ok(())
+    }
+
+    /// Ends a dispute: confirm the GM's ranking, replace it, or void the game. After a void each
+    /// depositor claims their entry fee with `claim_refund`.
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        resolution: DisputeResolution,
+    ) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        require!(
+            game.status == GameStatus::Disputed,
+            SettlementError::InvalidGameStatus
+        );
+
+        match resolution {
+            DisputeResolution::Confirm => game.status = GameStatus::Settled,
+            DisputeResolution::Overturn { ranking } => {
+                validate_ranking(game, &ranking)?;
+                game.winner = ranking[0];
+                game.ranking = ranking;
+                game.status = GameStatus::Settled;
+            }
+            DisputeResolution::Void => game.status = GameStatus::Voided,
+        }
+        let game_id = game.game_id;
+
+        emit!(DisputeResolved {
+            game_id,
+            status: game.status,
+            ranking: game.ranking.clone(),
         });
 
+        if game.status == GameStatus::Voided {
+            emit!(GameVoided { game_id });
+        }
+
         Ok(())
     }
 
+    /// Moves an undisputed result to `Settled` once its challenge period is over and sweeps the
+    /// rake into the treasury, so the platform is paid whether or not anyone claims.
+    pub fn finalize_settlement(ctx: Context<FinalizeSettlement>) -> Result<()> {
+        finalize(&mut ctx.accounts.game)?;
+
+        let accounts = &mut *ctx.accounts;
+        let vault = vault_accounts(
+            &accounts.game,
+            &accounts.vault,
+            &accounts.mint,
+            &accounts.token_program,
+        )?;
+        let treasury = treasury_destination(&vault, &accounts.treasury, &accounts.treasury_vault)?;
+        collect_platform_fee(&mut accounts.game, &vault, &treasury)
+    }
+
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
-        let (game_id, winner) = {
+        let (game_id, winner, place, winner_share) = {
             let game = &mut ctx.accounts.game;
-            require!(
-                game.status == GameStatus::Settled,
-                SettlementError::InvalidGameStatus
-            );
-            require!(
-                ctx.accounts.winner.key() == game.winner,
-                SettlementError::NotWinner
-            );
-            require!(!game.winner_paid, SettlementError::AlreadyPaid);
-
-            game.winner_paid = true;
-            (game.game_id, game.winner)
+            finalize(game)?;
+            let winner = ctx.accounts.winner.key();
+            let place = game
+                .ranking
+                .iter()
+                .position(|p| *p == winner)
+                .filter(|place| *place < game.payout_bps.len())
+                .ok_or(SettlementError::NotWinner)?;
+            let seat = find_player_index(game, &winner)?;
+            require!(!game.paid[seat], SettlementError::AlreadyPaid);
+
+            game.paid[seat] = true;
+            (game.game_id, winner, place as u8, game.place_share(place))
         };
 
-        let total_pot = ENTRY_FEE * NUM_PLAYERS as u64;
-        let winner_share = total_pot * WINNER_BPS / 10_000;
-        let platform_share = total_pot - winner_share;
-
-        **ctx
-            .accounts
-            .game
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= winner_share;
-        **ctx
-            .accounts
-            .winner
-            .to_account_info()
-            .try_borrow_mut_lamports()? += winner_share;
-
-        **ctx
-            .accounts
-            .game
-            .to_account_info()
-            .try_borrow_mut_lamports()? -= platform_share;
-        **ctx
-            .accounts
-            .platform_fee_account
-            .to_account_info()
-            .try_borrow_mut_lamports()? += platform_share;
+        let accounts = &mut *ctx.accounts;
+        let vault = vault_accounts(
+            &accounts.game,
+            &accounts.vault,
+            &accounts.mint,
+            &accounts.token_program,
+        )?;
+        let winner_dest = match vault {
+            None => accounts.winner.to_account_info(),
+            Some(_) => accounts
+                .winner_token_account
+                .as_ref()
+                .ok_or(SettlementError::MissingTokenAccounts)?
+                .to_account_info(),
+        };
+        let treasury = treasury_destination(&vault, &accounts.treasury, &accounts.treasury_vault)?;
+        collect_platform_fee(&mut accounts.game, &vault, &treasury)?;
+        pay_out(&accounts.game, &vault, &winner_dest, winner_share)?;
 
         emit!(Withdrawn {
             game_id,
             winner,
+            place,
             amount: winner_share,
         });
 
         Ok(())
     }
 
+    /// Sends the treasury's balance to `platform_fee_addr`: lamports above rent when no mint is
+    /// passed, otherwise everything in the treasury token account for that mint.
+    pub fn withdraw_platform_fees(ctx: Context<WithdrawPlatformFees>) -> Result<()> {
+        let accounts = &ctx.accounts;
+        let (mint, amount) = match (
+            &accounts.mint,
+            &accounts.treasury_vault,
+            &accounts.platform_fee_token_account,
+            &accounts.token_program,
+        ) {
+            (None, None, None, None) => {
+                let treasury = accounts.treasury.to_account_info();
+                let amount = treasury
+                    .lamports()
+                    .saturating_sub(Rent::get()?.minimum_balance(treasury.data_len()));
+                **treasury.try_borrow_mut_lamports()? -= amount;
+                **accounts.platform_fee_account.try_borrow_mut_lamports()? += amount;
+                (Pubkey::default(), amount)
+            }
+            (Some(mint), Some(treasury_vault), Some(dest), Some(token_program)) => {
+                let mint_key = mint.key();
+                let seeds: &[&[u8]] = &[
+                    b"treasury",
+                    mint_key.as_ref(),
+                    &[ctx.bumps.treasury_vault.unwrap()],
+                ];
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        TransferChecked {
+                            from: treasury_vault.to_account_info(),
+                            mint: mint.to_account_info(),
+                            to: dest.to_account_info(),
+                            authority: treasury_vault.to_account_info(),
+                        },
+                        &[seeds],
+                    ),
+                    treasury_vault.amount,
+                    mint.decimals,
+                )?;
+                (mint_key, treasury_vault.amount)
+            }
+            _ => return err!(SettlementError::MissingTokenAccounts),
+        };
+
+        emit!(PlatformFeesWithdrawn { mint, amount });
+
+        Ok(())
+    }
+
+    /// Voids a game whose reveals timed out. Depositors who did not reveal forfeit their entry
+    /// fee: the treasury takes `forfeit_treasury_bps` of it and the revealers split the rest.
+    /// Revealers then claim their entry fee plus that share with `claim_refund`. If nobody
+    /// revealed, everyone is refunded and the treasury account can be left out.
     pub fn void_game(ctx: Context<VoidGame>) -> Result<()> {
-        let (game_id, to_refund) = {
+        let (game_id, forfeit) = {
             let game = &mut ctx.accounts.game;
             let clock = Clock::get()?;
 
@@ -448,24 +1631,52 @@ pub mod monopoly_settlement {
             );
 
             game.status = GameStatus::Voided;
-            let to_refund: Vec<Pubkey> = ctx
-                .remaining_accounts
-                .iter()
-                .filter(|r| is_player(game, &r.key()) && has_deposit(game, &r.key()))
-                .map(|r| r.key())
-                .collect();
-            (game.game_id, to_refund)
+            let withheld = game.deposit_count - game.reveal_count;
+            let forfeit = if game.reveal_count > 0 && withheld > 0 {
+                let forfeited = game.entry_fee * withheld as u64;
+                let to_revealers = forfeited
+                    - forfeited * game.forfeit_treasury_bps as u64 / BPS_DENOMINATOR;
+                let share = to_revealers / game.reveal_count as u64;
+                game.forfeited_count = withheld;
+                game.forfeit_share = share;
+                let non_revealers = game
+                    .players
+                    .iter()
+                    .enumerate()
+                    .filter(|(seat, p)| has_deposit(game, p) && game.is_forfeited(*seat))
+                    .map(|(_, p)| *p)
+                    .collect::<Vec<_>>();
+                // Rounding dust goes to the treasury
+                let treasury_amount = forfeited - share * game.reveal_count as u64;
+                Some((non_revealers, forfeited, share, treasury_amount))
+            } else {
+                None
+            };
+            (game.game_id, forfeit)
         };
 
-        for remaining in ctx.remaining_accounts.iter() {
-            if to_refund.contains(&remaining.key()) {
-                **ctx
-                    .accounts
-                    .game
-                    .to_account_info()
-                    .try_borrow_mut_lamports()? -= ENTRY_FEE;
-                **remaining.try_borrow_mut_lamports()? += ENTRY_FEE;
-            }
+        if let Some((non_revealers, forfeited, revealer_share, treasury_amount)) = forfeit {
+            let accounts = &ctx.accounts;
+            let vault = vault_accounts(
+                &accounts.game,
+                &accounts.vault,
+                &accounts.mint,
+                &accounts.token_program,
+            )?;
+            let treasury = accounts
+                .treasury
+                .as_ref()
+                .ok_or(SettlementError::MissingTreasury)?;
+            let treasury = treasury_destination(&vault, treasury, &accounts.treasury_vault)?;
+            pay_out(&accounts.game, &vault, &treasury, treasury_amount)?;
+
+            emit!(DepositsForfeited {
+                game_id,
+                non_revealers,
+                forfeited,
+                revealer_share,
+                treasury_amount,
+            });
         }
 
         emit!(GameVoided { game_id });
@@ -473,81 +1684,203 @@ pub mod monopoly_settlement {
         Ok(())
     }
 
+    /// Cancels a game that never filled before its deposit deadline; refunds as for `void_game`.
     pub fn cancel_game(ctx: Context<CancelGame>) -> Result<()> {
-        let (game_id, to_refund) = {
+        let game_id = {
             let game = &mut ctx.accounts.game;
             let clock = Clock::get()?;
 
             require!(
                 (game.status == GameStatus::Depositing || game.status == GameStatus::Open)
-                    && clock.unix_timestamp > game.created_at + DEPOSIT_TIMEOUT,
+                    && clock.unix_timestamp > game.deposit_deadline,
                 SettlementError::CannotCancel
             );
 
-            if game.status == GameStatus::Open {
-                let platform = &mut ctx.accounts.platform;
-                remove_from_open_games(platform, game.game_id);
-            }
+            remove_from_open_games(
+                &mut ctx.accounts.platform,
+                ctx.accounts.lobby_page.as_deref_mut(),
+                game,
+            )?;
 
             game.status = GameStatus::Voided;
-            let to_refund: Vec<Pubkey> = ctx
-                .remaining_accounts
-                .iter()
-                .filter(|r| has_deposit(game, &r.key()))
-                .map(|r| r.key())
-                .collect();
-            (game.game_id, to_refund)
+            game.game_id
         };
 
-        for remaining in ctx.remaining_accounts.iter() {
-            if to_refund.contains(&remaining.key()) {
-                **ctx
-                    .accounts
-                    .game
-                    .to_account_info()
-                    .try_borrow_mut_lamports()? -= ENTRY_FEE;
-                **remaining.try_borrow_mut_lamports()? += ENTRY_FEE;
-            }
-        }
-
         emit!(GameVoided { game_id });
 
         Ok(())
     }
 
+    /// Voids a started game the GM never settled; refunds as for `void_game`.
     pub fn emergency_void(ctx: Context<EmergencyVoid>) -> Result<()> {
-        let (game_id, to_refund) = {
+        let game_id = {
             let game = &mut ctx.accounts.game;
             let clock = Clock::get()?;
 
             require!(
                 game.status == GameStatus::Started
-                    && clock.unix_timestamp > game.started_at + GAME_TIMEOUT,
+                    && clock.unix_timestamp > game.game_deadline,
                 SettlementError::CannotEmergencyVoid
             );
 
             game.status = GameStatus::Voided;
-            let to_refund: Vec<Pubkey> = ctx
-                .remaining_accounts
-                .iter()
-                .filter(|r| is_player(game, &r.key()))
-                .map(|r| r.key())
-                .collect();
-            (game.game_id, to_refund)
+            game.game_id
         };
 
-        for remaining in ctx.remaining_accounts.iter() {
-            if to_refund.contains(&remaining.key()) {
-                **ctx
-                    .accounts
-                    .game
-                    .to_account_info()
-                    .try_borrow_mut_lamports()? -= ENTRY_FEE;
-                **remaining.try_borrow_mut_lamports()? += ENTRY_FEE;
-            }
+        emit!(GameVoided { game_id });
+
+        Ok(())
+    }
+
+    /// Returns a depositor's entry fee from a voided game, once per seat, plus their share of
+    /// any deposits forfeited by non-revealers.
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let (game_id, player, amount) = {
+            let game = &mut ctx.accounts.game;
+            require!(
+                game.status == GameStatus::Voided,
+                SettlementError::InvalidGameStatus
+            );
+            let player = ctx.accounts.player.key();
+            let seat = find_player_index(game, &player)?;
+            require!(has_deposit(game, &player), SettlementError::NotAPlayer);
+            require!(!game.is_forfeited(seat), SettlementError::DepositForfeited);
+            require!(!game.paid[seat], SettlementError::AlreadyPaid);
+
+            game.paid[seat] = true;
+            (game.game_id, player, game.entry_fee + game.forfeit_share)
+        };
+
+        let accounts = &ctx.accounts;
+        let vault = vault_accounts(
+            &accounts.game,
+            &accounts.vault,
+            &accounts.mint,
+            &accounts.token_program,
+        )?;
+        let dest = match vault {
+            None => accounts.player.to_account_info(),
+            Some(_) => accounts
+                .player_token_account
+                .as_ref()
+                .ok_or(SettlementError::MissingTokenAccounts)?
+                .to_account_info(),
+        };
+        pay_out(&accounts.game, &vault, &dest, amount)?;
+
+        emit!(RefundClaimed {
+            game_id,
+            player,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Burns a player's in-game CLAW once the game is over and returns the account rent to them.
+    pub fn reclaim_claw(ctx: Context<ReclaimClaw>) -> Result<()> {
+        let game = &mut ctx.accounts.game;
+        require!(
+            matches!(
+                game.status,
+                GameStatus::PendingSettlement
+                    | GameStatus::Disputed
+                    | GameStatus::Settled
+                    | GameStatus::Voided
+            ),
+            SettlementError::InvalidGameStatus
+        );
+        game.claw_accounts_open -= 1;
+        let game = &ctx.accounts.game;
+
+        let game_id = game.game_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[game.bump]];
+        let amount = ctx.accounts.player_claw.amount;
+        let token_program = ctx.accounts.claw_token_program.to_account_info();
+        if amount > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    token_program.clone(),
+                    Burn {
+                        mint: ctx.accounts.claw_mint.to_account_info(),
+                        from: ctx.accounts.player_claw.to_account_info(),
+                        authority: game.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+            )?;
         }
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_program,
+            CloseAccount {
+                account: ctx.accounts.player_claw.to_account_info(),
+                destination: ctx.accounts.player.to_account_info(),
+                authority: game.to_account_info(),
+            },
+            &[seeds],
+        ))?;
+
+        emit!(ClawReclaimed {
+            game_id: game.game_id,
+            player: ctx.accounts.player.key(),
+            amount,
+        });
 
-        emit!(GameVoided { game_id });
+        Ok(())
+    }
+
+    /// Closes a checkpoint once its game is over, returning rent to whoever first paid for it.
+    pub fn close_checkpoint(ctx: Context<CloseCheckpoint>) -> Result<()> {
+        let game = &ctx.accounts.game;
+        require!(
+            game.status == GameStatus::Settled || game.status == GameStatus::Voided,
+            SettlementError::CannotClose
+        );
+        Ok(())
+    }
+
+    /// Closes a finished game, and its token vault if any, returning rent to the GM that created
+    /// it. The checkpoint must be closed first.
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        let game = &ctx.accounts.game;
+        require!(
+            game.payouts_complete()
+                && game.claw_accounts_open == 0
+                && ctx.accounts.checkpoint.data_is_empty(),
+            SettlementError::CannotClose
+        );
+
+        if let Some(v) = vault_accounts(
+            game,
+            &ctx.accounts.vault,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_program,
+        )? {
+            let game_id = game.game_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[game.bump]];
+            token_interface::close_account(CpiContext::new_with_signer(
+                v.token_program.to_account_info(),
+                CloseAccount {
+                    account: v.vault.to_account_info(),
+                    destination: ctx.accounts.rent_payer.to_account_info(),
+                    authority: game.to_account_info(),
+                },
+                &[seeds],
+            ))?;
+        }
+
+        emit!(GameClosed {
+            game_id: game.game_id,
+            status: game.status,
+            players: game.players.clone(),
+            ranking: game.ranking.clone(),
+            dice_seed: game.dice_seed,
+            game_log_hash: game.game_log_hash,
+            entry_fee: game.entry_fee,
+            mint: game.mint,
+            started_at: game.started_at,
+        });
 
         Ok(())
     }
@@ -562,6 +1895,43 @@ pub mod monopoly_settlement {
         Ok(())
     }
 
+    pub fn set_timeouts(
+        ctx: Context<AdminUpdate>,
+        defaults: Timeouts,
+        min: Timeouts,
+        max: Timeouts,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        require!(
+            min.reveal > 0
+                && min.deposit > 0
+                && min.game > 0
+                && min.challenge > 0
+                && max.is_within(&min, &Timeouts::MAX)
+                && defaults.is_within(&min, &max),
+            SettlementError::InvalidTimeouts
+        );
+        platform.default_timeouts = defaults;
+        platform.min_timeouts = min;
+        platform.max_timeouts = max;
+        Ok(())
+    }
+
+    pub fn set_platform_fee_bps(ctx: Context<AdminUpdate>, new_bps: u16) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        require!(new_bps <= MAX_PLATFORM_FEE_BPS, SettlementError::FeeTooHigh);
+        platform.platform_fee_bps = new_bps;
+        Ok(())
+    }
+
     pub fn set_platform_fee_addr(ctx: Context<AdminUpdate>, new_addr: Pubkey) -> Result<()> {
         let platform = &mut ctx.accounts.platform;
         require!(
@@ -571,157 +1941,1242 @@ pub mod monopoly_settlement {
         platform.platform_fee_addr = new_addr;
         Ok(())
     }
-}
 
-// ========== HELPERS ==========
+    /// Sets how forfeited deposits are split; games keep the split they were created with.
+    pub fn set_forfeit_treasury_bps(ctx: Context<AdminUpdate>, new_bps: u16) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        require!(
+            new_bps as u64 <= BPS_DENOMINATOR,
+            SettlementError::InvalidForfeitSplit
+        );
+        platform.forfeit_treasury_bps = new_bps;
+        Ok(())
+    }
 
-fn find_player_index(game: &GameState, player: &Pubkey) -> Result<usize> {
-    for i in 0..NUM_PLAYERS {
-        if game.players[i] == *player {
-            return Ok(i);
-        }
+    /// Starts an owner transfer. Nothing changes until `new_owner` accepts, so a mistyped key
+    /// cannot lock the platform; proposing again replaces the pending owner.
+    pub fn propose_owner(ctx: Context<AdminUpdate>, new_owner: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        platform.pending_owner = new_owner;
+        Ok(())
     }
-    Err(SettlementError::NotAPlayer.into())
-}
 
-fn is_player(game: &GameState, addr: &Pubkey) -> bool {
-    game.players.iter().any(|p| p == addr)
-}
+    pub fn cancel_owner_proposal(ctx: Context<AdminUpdate>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        platform.pending_owner = Pubkey::default();
+        Ok(())
+    }
 
-fn has_deposit(game: &GameState, addr: &Pubkey) -> bool {
-    for i in 0..NUM_PLAYERS {
-        if game.players[i] == *addr && game.commit_hashes[i] != [0u8; 32] {
-            return true;
-        }
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        let new_owner = ctx.accounts.new_owner.key();
+        require!(
+            platform.pending_owner == new_owner,
+            SettlementError::NotPendingOwner
+        );
+        let previous_owner = platform.owner;
+        platform.owner = new_owner;
+        platform.pending_owner = Pubkey::default();
+
+        emit!(OwnerTransferred {
+            previous_owner,
+            new_owner,
+        });
+
+        Ok(())
     }
-    false
-}
 
-fn remove_from_open_games(platform: &mut PlatformConfig, game_id: u64) {
-    let count = platform.open_game_count as usize;
-    for i in 0..count {
-        if platform.open_game_ids[i] == game_id {
-            platform.open_game_ids[i] = platform.open_game_ids[count - 1];
-            platform.open_game_ids[count - 1] = 0;
-            platform.open_game_count -= 1;
-            return;
+    /// Sets the paused scopes (`PAUSE_*` bits). The guardian can only add scopes; the owner can
+    /// also lift them.
+    pub fn set_paused(ctx: Context<PauseUpdate>, paused: u8) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        let authority = ctx.accounts.authority.key();
+        let only_adds = paused & platform.paused == platform.paused;
+        require!(
+            authority == platform.owner || (authority == platform.guardian && only_adds),
+            SettlementError::NotGuardian
+        );
+        platform.paused = paused & PAUSE_ALL;
+
+        emit!(PauseUpdated {
+            paused: platform.paused,
+            authority,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_guardian(ctx: Context<AdminUpdate>, new_guardian: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        platform.guardian = new_guardian;
+        Ok(())
+    }
+
+    /// Replaces the GM committee. An empty committee with threshold 0 lets the GM settle alone.
+    pub fn set_gm_committee(
+        ctx: Context<AdminUpdate>,
+        committee: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        require!(
+            committee.len() <= MAX_GM_COMMITTEE
+                && (threshold as usize) <= committee.len()
+                && (threshold > 0) == !committee.is_empty(),
+            SettlementError::InvalidCommittee
+        );
+        for (i, member) in committee.iter().enumerate() {
+            require!(
+                *member != Pubkey::default() && !committee[..i].contains(member),
+                SettlementError::InvalidCommittee
+            );
         }
+        platform.gm_committee = committee;
+        platform.gm_threshold = threshold;
+        Ok(())
     }
+
+    /// Sets the key that may resolve disputes besides the owner. The default key removes it.
+    pub fn set_arbiter(ctx: Context<AdminUpdate>, new_arbiter: Pubkey) -> Result<()> {
+        let platform = &mut ctx.accounts.platform;
+        require!(
+            platform.owner == ctx.accounts.owner.key(),
+            SettlementError::NotOwner
+        );
+        platform.arbiter = new_arbiter;
+        Ok(())
+    }
+}
+
+// ========== HELPERS ==========
+
+fn find_player_index(game: &GameState, player: &Pubkey) -> Result<usize> {
+    game.players
+        .iter()
+        .position(|p| p == player && *p != Pubkey::default())
+        .ok_or_else(|| SettlementError::NotAPlayer.into())
+}
+
+/// A ranking lists every depositor exactly once, winner first.
+fn validate_ranking(game: &GameState, ranking: &[Pubkey]) -> Result<()> {
+    require!(
+        ranking.len() == game.deposit_count as usize,
+        SettlementError::InvalidRanking
+    );
+    for (i, p) in ranking.iter().enumerate() {
+        require!(is_player(game, p), SettlementError::WinnerNotPlayer);
+        // A fixed-roster game started at min_players can seat players who never paid in
+        require!(
+            has_deposit(game, p) && !ranking[..i].contains(p),
+            SettlementError::InvalidRanking
+        );
+    }
+    Ok(())
+}
+
+/// The commit hash a player's secret must match under the game's commit scheme.
+fn commitment(game: &GameState, player: &Pubkey, secret: &[u8; 32]) -> [u8; 32] {
+    match game.commit_scheme {
+        0 => keccak::hash(secret).to_bytes(),
+        _ => keccak::hashv(&[&game.game_id.to_le_bytes(), player.as_ref(), secret]).to_bytes(),
+    }
+}
+
+fn is_player(game: &GameState, addr: &Pubkey) -> bool {
+    *addr != Pubkey::default() && game.players.iter().any(|p| p == addr)
+}
+
+fn has_deposit(game: &GameState, addr: &Pubkey) -> bool {
+    game.players
+        .iter()
+        .zip(game.commit_hashes.iter())
+        .any(|(p, c)| p == addr && *c != [0u8; 32])
+}
+
+fn begin_reveal(
+    game: &mut GameState,
+    platform: &mut PlatformConfig,
+    lobby: Option<&mut LobbyPage>,
+) -> Result<()> {
+    remove_from_open_games(platform, lobby, game)?;
+
+    let clock = Clock::get()?;
+    game.status = GameStatus::Revealing;
+    game.reveal_deadline = clock.unix_timestamp + game.timeouts.reveal;
+
+    emit!(AllDeposited {
+        game_id: game.game_id,
+        reveal_deadline: game.reveal_deadline,
+    });
+    Ok(())
+}
+
+/// Checks the table parameters shared by open and fixed-roster games. Returns the effective
+/// minimum player count and timeouts.
+fn validate_table(
+    platform: &PlatformConfig,
+    entry_fee: u64,
+    max_players: u8,
+    min_players: Option<u8>,
+    timeouts: Option<Timeouts>,
+    payout_bps: &[u16],
+) -> Result<(u8, Timeouts)> {
+    let min_players = min_players.unwrap_or(max_players);
+    require!(
+        (MIN_PLAYERS..=MAX_PLAYERS).contains(&(max_players as usize))
+            && (MIN_PLAYERS as u8..=max_players).contains(&min_players),
+        SettlementError::InvalidPlayerCount
+    );
+    require!(
+        entry_fee > 0 && entry_fee.checked_mul(max_players as u64).is_some(),
+        SettlementError::WrongEntryFee
+    );
+    require!(
+        !payout_bps.is_empty()
+            && payout_bps.len() <= min_players as usize
+            && payout_bps.iter().map(|b| *b as u64).sum::<u64>() == BPS_DENOMINATOR,
+        SettlementError::InvalidPayoutTable
+    );
+    let timeouts = match timeouts {
+        Some(t) => {
+            require!(
+                t.is_within(&platform.min_timeouts, &platform.max_timeouts),
+                SettlementError::InvalidTimeouts
+            );
+            t
+        }
+        None => platform.default_timeouts,
+    };
+    Ok((min_players, timeouts))
+}
+
+/// Rejects checkpoints the engine could not have produced, both on their own and as a step on
+/// from `prev`. Engine player `i` is the `i`-th depositor in seat order.
+fn validate_checkpoint(
+    game: &GameState,
+    entry: &CheckpointEntry,
+    prev: Option<&CheckpointEntry>,
+) -> Result<()> {
+    let seats = game.deposit_count as usize;
+    let invalid = || error!(SettlementError::InvalidCheckpointState);
+
+    let meta = CheckpointMeta::unpack(entry.meta_packed).ok_or_else(invalid)?;
+    let mut players = [CheckpointPlayer::default(); MAX_PLAYERS];
+    for (p, slot) in players.iter_mut().zip(&entry.players_packed[..seats]) {
+        *p = CheckpointPlayer::unpack(*slot).ok_or_else(invalid)?;
+    }
+    let players = &players[..seats];
+    require!(
+        meta.round as u64 == entry.round
+            && (meta.current_player as usize) < seats
+            && meta.alive_count as usize == players.iter().filter(|p| p.alive).count()
+            && players.iter().all(|p| p.position < BOARD_SIZE)
+            && entry.players_packed[seats..].iter().all(|s| *s == 0),
+        SettlementError::InvalidCheckpointState
+    );
+
+    let properties = unpack_properties(entry.properties_packed).ok_or_else(invalid)?;
+    for (p, houses) in properties.iter().zip(entry.houses) {
+        let valid = match p.owner {
+            None => !p.mortgaged && houses == 0,
+            // Bankrupt players lose everything they own
+            Some(owner) => players.get(owner as usize).is_some_and(|o| o.alive)
+                && houses <= MAX_HOUSES
+                && !(p.mortgaged && houses > 0),
+        };
+        require!(valid, SettlementError::InvalidCheckpointState);
+    }
+
+    // v1 entries only carried two player slots, so there is nothing reliable to compare against
+    let Some(prev) = prev.filter(|p| p.version >= 2) else {
+        return Ok(());
+    };
+    let was_alive = |i: usize| {
+        CheckpointPlayer::unpack(prev.players_packed[i]).is_some_and(|p| p.alive)
+    };
+    let prev_meta = CheckpointMeta::unpack(prev.meta_packed).unwrap_or_default();
+    require!(
+        meta.turn >= prev_meta.turn
+            && meta.alive_count <= prev_meta.alive_count
+            && players.iter().enumerate().all(|(i, p)| was_alive(i) || !p.alive),
+        SettlementError::InvalidCheckpointTransition
+    );
+    Ok(())
+}
+
+struct NewGame {
+    status: GameStatus,
+    players: Vec<Pubkey>,
+    entry_fee: u64,
+    min_players: u8,
+    timeouts: Timeouts,
+    payout_bps: Vec<u16>,
+    mint: Pubkey,
+}
+
+/// Fills in a freshly created game account and claims the next game id.
+fn init_game(
+    game: &mut GameState,
+    platform: &mut PlatformConfig,
+    bump: u8,
+    new: NewGame,
+) -> Result<u64> {
+    let game_id = platform.game_count;
+    let clock = Clock::get()?;
+    let seats = new.players.len();
+
+    game.game_id = game_id;
+    game.status = new.status;
+    game.players = new.players;
+    game.commit_hashes = vec![[0u8; 32]; seats];
+    game.revealed_secrets = vec![[0u8; 32]; seats];
+    game.deposit_count = 0;
+    game.reveal_count = 0;
+    game.dice_seed = [0u8; 32];
+    game.winner = Pubkey::default();
+    game.game_log_hash = [0u8; 32];
+    game.reveal_deadline = 0;
+    game.created_at = clock.unix_timestamp;
+    game.started_at = 0;
+    game.paid = vec![false; seats];
+    game.bump = bump;
+    game.entry_fee = new.entry_fee;
+    game.max_players = seats as u8;
+    game.min_players = new.min_players;
+    game.timeouts = new.timeouts;
+    game.deposit_deadline = clock.unix_timestamp + new.timeouts.deposit;
+    game.game_deadline = 0;
+    game.platform_fee_bps = platform.platform_fee_bps;
+    game.payout_bps = new.payout_bps;
+    game.ranking = Vec::new();
+    game.platform_paid = false;
+    game.mint = new.mint;
+    game.claw_mint = platform.claw_mint;
+    game.rent_payer = platform.gm_signer;
+    game.claw_accounts_open = 0;
+    game.challenge_deadline = 0;
+    game.disputed_by = Pubkey::default();
+    game.version = GAME_VERSION;
+    game.forfeit_treasury_bps = platform.forfeit_treasury_bps;
+    game.forfeited_count = 0;
+    game.forfeit_share = 0;
+    game.commit_scheme = COMMIT_SCHEME;
+    game.claw_bumps = vec![0u8; seats];
+
+    platform.game_count += 1;
+    Ok(game_id)
+}
+
+/// Reallocs a program account up to `size`, zero-filling the new bytes; `payer` covers the rent
+/// for the added bytes. Accounts already that large are left alone.
+fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    size: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if info.data_len() >= size {
+        return Ok(());
+    }
+    // Charged on the size difference alone: a SOL game's lamports also hold its pot, which
+    // must not end up paying for the extra rent
+    let rent = Rent::get()?;
+    let shortfall = rent.minimum_balance(size) - rent.minimum_balance(info.data_len());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    info.realloc(size, true)?;
+    Ok(())
+}
+
+/// Key of the `mint` in a treasury vault's seeds. Anchor evaluates those seeds both on the
+/// optional account and on the unwrapped one, so the helper accepts either.
+trait MintKey {
+    fn mint_key(&self) -> Pubkey;
+}
+
+impl MintKey for InterfaceAccount<'_, Mint> {
+    fn mint_key(&self) -> Pubkey {
+        self.key()
+    }
+}
+
+impl MintKey for Option<InterfaceAccount<'_, Mint>> {
+    fn mint_key(&self) -> Pubkey {
+        self.as_ref().map(|m| m.key()).unwrap_or_default()
+    }
+}
+
+/// Drops an open game from its lobby page. Games that are not listed (fixed-roster games) need
+/// no lobby page.
+fn remove_from_open_games(
+    platform: &mut PlatformConfig,
+    lobby: Option<&mut LobbyPage>,
+    game: &GameState,
+) -> Result<()> {
+    if game.status != GameStatus::Open {
+        return Ok(());
+    }
+    let lobby = lobby.ok_or(SettlementError::InvalidLobbyPage)?;
+    if let Some(i) = lobby.game_ids.iter().position(|id| *id == game.game_id) {
+        lobby.game_ids.swap_remove(i);
+        platform.open_game_count -= 1;
+    }
+    Ok(())
+}
+
+/// Mints STARTING_CLAW into each depositing player's CLAW account. `remaining` must hold those
+/// accounts in seat order.
+fn mint_starting_claw<'info>(
+    game: &GameState,
+    claw_mint: &Option<InterfaceAccount<'info, Mint>>,
+    token_program: &Option<Interface<'info, TokenInterface>>,
+    remaining: &[AccountInfo<'info>],
+) -> Result<()> {
+    let (Some(claw_mint), Some(token_program)) = (claw_mint, token_program) else {
+        return err!(SettlementError::InvalidClawAccounts);
+    };
+    let depositors: Vec<(usize, &Pubkey)> = game
+        .players
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| has_deposit(game, p))
+        .collect();
+    require!(
+        depositors.len() == remaining.len(),
+        SettlementError::InvalidClawAccounts
+    );
+
+    let game_id = game.game_id.to_le_bytes();
+    let (_, mint_bump) = Pubkey::find_program_address(&[b"claw_mint"], &crate::ID);
+    let mint_seeds: &[&[u8]] = &[b"claw_mint", &[mint_bump]];
+    for ((seat, player), account) in depositors.into_iter().zip(remaining) {
+        let seeds: &[&[u8]] = &[b"claw", game_id.as_ref(), player.as_ref()];
+        let expected = match game.claw_bumps.get(seat) {
+            Some(bump) => {
+                Pubkey::create_program_address(&[seeds, &[&[*bump]]].concat(), &crate::ID)
+                    .map_err(|_| error!(SettlementError::InvalidClawAccounts))?
+            }
+            // Deposited before bumps were recorded
+            None => Pubkey::find_program_address(seeds, &crate::ID).0,
+        };
+        require_keys_eq!(account.key(), expected, SettlementError::InvalidClawAccounts);
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                MintTo {
+                    mint: claw_mint.to_account_info(),
+                    to: account.clone(),
+                    authority: claw_mint.to_account_info(),
+                },
+                &[mint_seeds],
+            ),
+            STARTING_CLAW,
+        )?;
+    }
+    Ok(())
+}
+
+struct VaultAccounts<'a, 'info> {
+    vault: &'a InterfaceAccount<'info, TokenAccount>,
+    mint: &'a InterfaceAccount<'info, Mint>,
+    token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Resolves the optional token accounts of a payout context. SOL games yield `None`.
+fn vault_accounts<'a, 'info>(
+    game: &GameState,
+    vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<Option<VaultAccounts<'a, 'info>>> {
+    if !game.is_token_game() {
+        return Ok(None);
+    }
+    match (vault, mint, token_program) {
+        (Some(vault), Some(mint), Some(token_program)) => Ok(Some(VaultAccounts {
+            vault,
+            mint,
+            token_program,
+        })),
+        _ => err!(SettlementError::MissingTokenAccounts),
+    }
+}
+
+/// Moves `amount` out of the pot: lamports off the game account, or tokens out of the vault
+/// signed for by the game PDA.
+fn pay_out<'info>(
+    game: &Account<'info, GameState>,
+    vault: &Option<VaultAccounts<'_, 'info>>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    match vault {
+        None => {
+            **game.to_account_info().try_borrow_mut_lamports()? -= amount;
+            **to.try_borrow_mut_lamports()? += amount;
+        }
+        Some(v) => {
+            let game_id = game.game_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[b"game", game_id.as_ref(), &[game.bump]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    v.token_program.to_account_info(),
+                    TransferChecked {
+                        from: v.vault.to_account_info(),
+                        mint: v.mint.to_account_info(),
+                        to: to.clone(),
+                        authority: game.to_account_info(),
+                    },
+                    &[seeds],
+                ),
+                amount,
+                v.mint.decimals,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Settles an undisputed result once its challenge period has passed. Settled games pass through.
+fn finalize(game: &mut GameState) -> Result<()> {
+    if game.status == GameStatus::PendingSettlement {
+        require!(
+            Clock::get()?.unix_timestamp > game.challenge_deadline,
+            SettlementError::ChallengePeriodActive
+        );
+        game.status = GameStatus::Settled;
+    }
+    require!(
+        game.status == GameStatus::Settled,
+        SettlementError::InvalidGameStatus
+    );
+    Ok(())
+}
+
+/// Where a game's rake goes: the treasury itself for SOL games, or its token account for the
+/// game mint.
+fn treasury_destination<'info>(
+    vault: &Option<VaultAccounts<'_, 'info>>,
+    treasury: &Account<'info, Treasury>,
+    treasury_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+) -> Result<AccountInfo<'info>> {
+    match vault {
+        None => Ok(treasury.to_account_info()),
+        Some(_) => Ok(treasury_vault
+            .as_ref()
+            .ok_or(SettlementError::MissingTokenAccounts)?
+            .to_account_info()),
+    }
+}
+
+/// Moves a settled game's rake into the treasury, once.
+fn collect_platform_fee<'info>(
+    game: &mut Account<'info, GameState>,
+    vault: &Option<VaultAccounts<'_, 'info>>,
+    treasury: &AccountInfo<'info>,
+) -> Result<()> {
+    if game.platform_paid {
+        return Ok(());
+    }
+    game.platform_paid = true;
+    let amount = game.platform_share();
+    pay_out(game, vault, treasury, amount)?;
+
+    emit!(PlatformFeeCollected {
+        game_id: game.game_id,
+        mint: game.mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+// ========== ACCOUNT CONTEXTS ==========
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = PlatformConfig::SIZE,
+        seeds = [b"platform"],
+        bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitClawMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"claw_mint"],
+        bump,
+        mint::decimals = CLAW_DECIMALS,
+        mint::authority = claw_mint,
+        mint::token_program = token_program,
+    )]
+    pub claw_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitTreasury<'info> {
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = owner,
+        space = Treasury::SIZE,
+        seeds = [b"treasury"],
+        bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    entry_fee: u64,
+    max_players: u8,
+    min_players: Option<u8>,
+    timeouts: Option<Timeouts>,
+    payout_bps: Vec<u16>,
+    page: u32,
+)]
+pub struct CreateOpenGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = gm,
+        space = GameState::space(max_players as usize),
+        seeds = [b"game", platform.game_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init_if_needed,
+        payer = gm,
+        space = LobbyPage::SIZE,
+        seeds = [b"lobby", page.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub lobby_page: Account<'info, LobbyPage>,
+    #[account(mut)]
+    pub gm: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token games only; SOL games omit the accounts below
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = gm,
+        seeds = [b"vault", platform.game_count.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = game,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// The treasury's token account for this mint, opened by the first game that uses it
+    #[account(
+        init_if_needed,
+        payer = gm,
+        seeds = [b"treasury", mint.mint_key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(players: Vec<Pubkey>)]
+pub struct CreateGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        init,
+        payer = gm,
+        space = GameState::space(players.len()),
+        seeds = [b"game", platform.game_count.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(mut)]
+    pub gm: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token games only; SOL games omit the accounts below
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = gm,
+        seeds = [b"vault", platform.game_count.to_le_bytes().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = game,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// The treasury's token account for this mint, opened by the first game that uses it
+    #[account(
+        init_if_needed,
+        payer = gm,
+        seeds = [b"treasury", mint.mint_key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = treasury_vault,
+        token::token_program = token_program,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct DepositAndCommit<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    /// Open games only; the lobby page listing this game
+    #[account(
+        mut,
+        seeds = [b"lobby", game.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Option<Account<'info, LobbyPage>>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// Token games only; SOL games omit the accounts below
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// Games with CLAW only
+    #[account(address = game.claw_mint @ SettlementError::WrongMint)]
+    pub claw_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init,
+        payer = player,
+        seeds = [b"claw", game.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+        token::mint = claw_mint,
+        token::authority = game,
+        token::token_program = claw_token_program,
+    )]
+    pub player_claw: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub claw_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(mut)]
+    pub player: Signer<'info>,
+    /// Token games only
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    /// Games with CLAW only
+    #[account(
+        mut,
+        seeds = [b"claw", game.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_claw: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub claw_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct StartReveal<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.gm_signer == gm.key() @ SettlementError::NotGM,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    /// Open games only; the lobby page listing this game
+    #[account(
+        mut,
+        seeds = [b"lobby", game.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Option<Account<'info, LobbyPage>>,
+    pub gm: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    pub player: Signer<'info>,
+    /// Games with CLAW only; the final reveal passes every depositor's CLAW account in
+    /// remaining_accounts
+    #[account(mut, address = game.claw_mint @ SettlementError::WrongMint)]
+    pub claw_mint: Option<InterfaceAccount<'info, Mint>>,
+    pub claw_token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct WriteCheckpoint<'info> {
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.gm_signer == gm.key() @ SettlementError::NotGM,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(
+        init_if_needed,
+        payer = gm,
+        space = GameCheckpoint::SIZE,
+        seeds = [b"checkpoint", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub checkpoint: Box<Account<'info, GameCheckpoint>>,
+    #[account(mut)]
+    pub gm: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReadDice<'info> {
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCheckpoint<'info> {
+    #[account(
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    /// CHECK: may still hold a single-checkpoint layout; decoded with GameCheckpoint::unpack
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"checkpoint", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub checkpoint: AccountInfo<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePlatform<'info> {
+    /// CHECK: may still hold an older layout; owner is checked after it is upgraded
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"platform"],
+        bump,
+    )]
+    pub platform: AccountInfo<'info>,
+    /// Baseline layout only; lobby page 0, created to hold its open games
+    #[account(
+        init,
+        payer = owner,
+        space = LobbyPage::SIZE,
+        seeds = [b"lobby", 0u32.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub lobby_page: Option<Account<'info, LobbyPage>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct MigrateGame<'info> {
+    #[account(
+        seeds = [b"platform"],
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ SettlementError::NotOwner,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    /// CHECK: may still hold an older layout; upgraded in place
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"game", game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub game: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
-// ========== ACCOUNT CONTEXTS ==========
-
 #[derive(Accounts)]
-pub struct Initialize<'info> {
+#[instruction(page: u32)]
+pub struct MigrateLobbyPage<'info> {
     #[account(
-        init,
-        payer = owner,
-        space = PlatformConfig::SIZE,
         seeds = [b"platform"],
-        bump,
+        bump = platform.bump,
+        constraint = platform.owner == owner.key() @ SettlementError::NotOwner,
     )]
     pub platform: Account<'info, PlatformConfig>,
+    /// CHECK: may still hold an older layout; upgraded in place
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"lobby", page.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub lobby_page: AccountInfo<'info>,
     #[account(mut)]
     pub owner: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct CreateOpenGame<'info> {
+pub struct SettleGame<'info> {
     #[account(
-        mut,
         seeds = [b"platform"],
         bump = platform.bump,
+        constraint = platform.gm_signer == gm.key() @ SettlementError::NotGM,
     )]
     pub platform: Account<'info, PlatformConfig>,
     #[account(
-        init,
-        payer = gm,
-        space = GameState::SIZE,
-        seeds = [b"game", platform.game_count.to_le_bytes().as_ref()],
-        bump,
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
-    #[account(mut)]
     pub gm: Signer<'info>,
-    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct DepositAndCommit<'info> {
+pub struct DisputeSettlement<'info> {
     #[account(
         mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
     #[account(
-        mut,
         seeds = [b"platform"],
         bump = platform.bump,
+        constraint = arbiter.key() == platform.owner
+            || arbiter.key() == platform.arbiter @ SettlementError::NotArbiter,
     )]
     pub platform: Account<'info, PlatformConfig>,
-    #[account(mut)]
-    pub player: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    pub arbiter: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct RevealSeed<'info> {
+pub struct FinalizeSettlement<'info> {
     #[account(
         mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
-    pub player: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// Token games only; SOL games omit the accounts below
+    #[account(
+        mut,
+        seeds = [b"treasury", game.mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct WriteCheckpoint<'info> {
+pub struct Withdraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    /// Takes the rake if this is the game's first claim
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// Token games only; SOL games omit the accounts below
+    #[account(
+        mut,
+        token::mint = game.mint,
+        token::authority = winner,
+    )]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"treasury", game.mint.as_ref()],
+        bump,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPlatformFees<'info> {
     #[account(
         seeds = [b"platform"],
         bump = platform.bump,
-        constraint = platform.gm_signer == gm.key() @ SettlementError::NotGM,
+        constraint = platform.owner == owner.key() @ SettlementError::NotOwner,
     )]
     pub platform: Account<'info, PlatformConfig>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Account<'info, Treasury>,
+    /// CHECK: platform fee recipient validated against PlatformConfig
+    #[account(
+        mut,
+        constraint = platform_fee_account.key() == platform.platform_fee_addr,
+    )]
+    pub platform_fee_account: AccountInfo<'info>,
+    /// Token fees only; SOL fees omit the accounts below
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [b"treasury", mint.mint_key().as_ref()],
+        bump,
+        token::mint = mint,
+    )]
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = platform.platform_fee_addr,
+    )]
+    pub platform_fee_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+}
+
+#[derive(Accounts)]
+pub struct VoidGame<'info> {
     #[account(
+        mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
+    pub caller: Signer<'info>,
+    /// Takes the treasury's cut of forfeited deposits; only needed when someone did not reveal
     #[account(
-        init_if_needed,
-        payer = gm,
-        space = GameCheckpoint::SIZE,
-        seeds = [b"checkpoint", game.game_id.to_le_bytes().as_ref()],
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump,
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    /// Token games only; SOL games omit the accounts below
+    #[account(
+        mut,
+        seeds = [b"treasury", game.mint.as_ref()],
         bump,
     )]
-    pub checkpoint: Account<'info, GameCheckpoint>,
-    #[account(mut)]
-    pub gm: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub treasury_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct SettleGame<'info> {
+pub struct CancelGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
+        bump = game.bump,
+    )]
+    pub game: Account<'info, GameState>,
     #[account(
+        mut,
         seeds = [b"platform"],
         bump = platform.bump,
-        constraint = platform.gm_signer == gm.key() @ SettlementError::NotGM,
     )]
     pub platform: Account<'info, PlatformConfig>,
+    /// Open games only; the lobby page listing this game
+    #[account(
+        mut,
+        seeds = [b"lobby", game.lobby_page.to_le_bytes().as_ref()],
+        bump = lobby_page.bump,
+    )]
+    pub lobby_page: Option<Account<'info, LobbyPage>>,
+    pub caller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyVoid<'info> {
     #[account(
         mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
-    pub gm: Signer<'info>,
+    pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Withdraw<'info> {
+pub struct ClaimRefund<'info> {
     #[account(
         mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
@@ -729,57 +3184,97 @@ pub struct Withdraw<'info> {
     )]
     pub game: Account<'info, GameState>,
     #[account(mut)]
-    pub winner: Signer<'info>,
-    /// CHECK: platform fee recipient validated against PlatformConfig
+    pub player: Signer<'info>,
+    /// Token games only; SOL games omit the accounts below
     #[account(
         mut,
-        constraint = platform_fee_account.key() == platform.platform_fee_addr,
+        token::mint = game.mint,
+        token::authority = player,
     )]
-    pub platform_fee_account: AccountInfo<'info>,
+    pub player_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        seeds = [b"platform"],
-        bump = platform.bump,
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
     )]
-    pub platform: Account<'info, PlatformConfig>,
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
-pub struct VoidGame<'info> {
+pub struct ReclaimClaw<'info> {
     #[account(
         mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
+    #[account(mut, address = game.claw_mint @ SettlementError::WrongMint)]
+    pub claw_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [b"claw", game.game_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub player_claw: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: receives the CLAW account rent; bound to player_claw by its seeds
+    #[account(mut)]
+    pub player: AccountInfo<'info>,
     pub caller: Signer<'info>,
+    pub claw_token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct CancelGame<'info> {
+pub struct CloseCheckpoint<'info> {
     #[account(
-        mut,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
     #[account(
         mut,
-        seeds = [b"platform"],
-        bump = platform.bump,
+        close = payer,
+        seeds = [b"checkpoint", game.game_id.to_le_bytes().as_ref()],
+        bump = checkpoint.bump,
     )]
-    pub platform: Account<'info, PlatformConfig>,
+    pub checkpoint: Box<Account<'info, GameCheckpoint>>,
+    /// CHECK: rent destination, pinned to the recorded payer
+    #[account(mut, address = checkpoint.payer)]
+    pub payer: AccountInfo<'info>,
     pub caller: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct EmergencyVoid<'info> {
+pub struct CloseGame<'info> {
     #[account(
         mut,
+        close = rent_payer,
         seeds = [b"game", game.game_id.to_le_bytes().as_ref()],
         bump = game.bump,
     )]
     pub game: Account<'info, GameState>,
+    /// CHECK: must already be closed (or never written); only its emptiness is read
+    #[account(
+        seeds = [b"checkpoint", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub checkpoint: AccountInfo<'info>,
+    /// CHECK: rent destination, pinned to the recorded payer
+    #[account(mut, address = game.rent_payer)]
+    pub rent_payer: AccountInfo<'info>,
     pub caller: Signer<'info>,
+    /// Token games only
+    #[account(
+        mut,
+        seeds = [b"vault", game.game_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = game.mint @ SettlementError::WrongMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(Accounts)]
@@ -793,11 +3288,44 @@ pub struct AdminUpdate<'info> {
     pub owner: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct PauseUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"platform"],
+        bump = platform.bump,
+    )]
+    pub platform: Account<'info, PlatformConfig>,
+    pub new_owner: Signer<'info>,
+}
+
 // ========== EVENTS ==========
 
 #[event]
 pub struct OpenGameCreated {
     pub game_id: u64,
+    pub entry_fee: u64,
+    pub max_players: u8,
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct GameCreated {
+    pub game_id: u64,
+    pub players: Vec<Pubkey>,
+    pub entry_fee: u64,
+    pub mint: Pubkey,
 }
 
 #[event]
@@ -807,6 +3335,13 @@ pub struct DepositAndCommitEvent {
     pub commit_hash: [u8; 32],
 }
 
+#[event]
+pub struct PlayerLeft {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct AllDeposited {
     pub game_id: u64,
@@ -831,21 +3366,111 @@ pub struct CheckpointWritten {
     pub round: u64,
 }
 
+#[event]
+pub struct CheckpointMigrated {
+    pub game_id: u64,
+    pub round: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
 #[event]
 pub struct GameSettledEvent {
     pub game_id: u64,
     pub winner: Pubkey,
+    pub ranking: Vec<Pubkey>,
     pub game_log_hash: [u8; 32],
+    pub challenge_deadline: i64,
+}
+
+#[event]
+pub struct SettlementDisputed {
+    pub game_id: u64,
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub game_id: u64,
+    pub status: GameStatus,
+    pub ranking: Vec<Pubkey>,
 }
 
 #[event]
 pub struct Withdrawn {
     pub game_id: u64,
     pub winner: Pubkey,
+    pub place: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlatformFeeCollected {
+    pub game_id: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlatformFeesWithdrawn {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub game_id: u64,
+    pub player: Pubkey,
     pub amount: u64,
 }
 
+#[event]
+pub struct DepositsForfeited {
+    pub game_id: u64,
+    pub non_revealers: Vec<Pubkey>,
+    pub forfeited: u64,
+    pub revealer_share: u64,
+    pub treasury_amount: u64,
+}
+
 #[event]
 pub struct GameVoided {
     pub game_id: u64,
 }
+
+#[event]
+pub struct ClawReclaimed {
+    pub game_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GameClosed {
+    pub game_id: u64,
+    pub status: GameStatus,
+    pub players: Vec<Pubkey>,
+    pub ranking: Vec<Pubkey>,
+    pub dice_seed: [u8; 32],
+    pub game_log_hash: [u8; 32],
+    pub entry_fee: u64,
+    pub mint: Pubkey,
+    pub started_at: i64,
+}
+
+#[event]
+pub struct OwnerTransferred {
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub paused: u8,
+    pub authority: Pubkey,
+}
@@ -0,0 +1,160 @@
+#!/usr/bin/env python3
+"""Writes legacy-game.json: game 1000000 in the baseline layout the program was first deployed
+with (four fixed seats, no vectors or version), open with two of its seats deposited. The seats
+are `Keypair.fromSeed` of 32 bytes of 1 and 2.
+
+With `platform`, writes legacy-platform.json instead: the platform config in the baseline layout,
+owned by the seed of 32 bytes of 4, listing game 1000000 as its only open game. Its game count
+stays 0 so the test suite numbers its own games from 0.
+
+With `checkpoint`, writes legacy-checkpoint.json: the game's checkpoint in the v1 layout, which
+has no payer.
+
+    python3 tests/fixtures/legacy_game.py > tests/fixtures/legacy-game.json
+    python3 tests/fixtures/legacy_game.py platform > tests/fixtures/legacy-platform.json
+    python3 tests/fixtures/legacy_game.py checkpoint > tests/fixtures/legacy-checkpoint.json
+"""
+
+import base64
+import hashlib
+import json
+import struct
+import sys
+
+from cryptography.hazmat.primitives.asymmetric.ed25519 import Ed25519PrivateKey
+from cryptography.hazmat.primitives.serialization import Encoding, PublicFormat
+
+PROGRAM_ID = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS"
+GAME_ID = 1_000_000
+ENTRY_FEE = 10_000_000
+SEATS = 4
+MAX_OPEN_GAMES = 20
+OPEN = 1
+
+B58 = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz"
+P = 2**255 - 19
+D = -121665 * pow(121666, P - 2, P) % P
+
+
+def b58encode(b: bytes) -> str:
+    n = int.from_bytes(b, "big")
+    out = ""
+    while n:
+        n, r = divmod(n, 58)
+        out = B58[r] + out
+    return "1" * (len(b) - len(b.lstrip(b"\0"))) + out
+
+
+def b58decode_key(s: str) -> bytes:
+    n = 0
+    for c in s:
+        n = n * 58 + B58.index(c)
+    return n.to_bytes(32, "big")
+
+
+def on_curve(b: bytes) -> bool:
+    y = int.from_bytes(b, "little") & ((1 << 255) - 1)
+    if y >= P:
+        return False
+    x2 = (y * y - 1) * pow(D * y * y + 1, P - 2, P) % P
+    return x2 == 0 or pow(x2, (P - 1) // 2, P) == 1
+
+
+def find_pda(seeds: list, program: bytes) -> tuple:
+    for bump in range(255, -1, -1):
+        h = hashlib.sha256(b"".join(seeds) + bytes([bump]) + program + b"ProgramDerivedAddress")
+        if not on_curve(h.digest()):
+            return h.digest(), bump
+    raise ValueError("no bump")
+
+
+def pubkey_from_seed(byte: int) -> bytes:
+    key = Ed25519PrivateKey.from_private_bytes(bytes([byte]) * 32)
+    return key.public_key().public_bytes(Encoding.Raw, PublicFormat.Raw)
+
+
+def discriminator(account: str) -> bytes:
+    return hashlib.sha256(f"account:{account}".encode()).digest()[:8]
+
+
+def rent(size: int) -> int:
+    return (128 + size) * 3480 * 2
+
+
+def dump(pubkey: bytes, data: bytes, lamports: int) -> None:
+    json.dump(
+        {
+            "pubkey": b58encode(pubkey),
+            "account": {
+                "lamports": lamports,
+                "data": [base64.b64encode(data).decode(), "base64"],
+                "owner": PROGRAM_ID,
+                "executable": False,
+                "rentEpoch": 0,
+                "space": len(data),
+            },
+        },
+        sys.stdout,
+        indent=2,
+    )
+    print()
+
+
+def checkpoint(program: bytes) -> None:
+    """`GameCheckpoint` in the v1 layout: one u128 for the first two player slots, no houses."""
+    address, bump = find_pda([b"checkpoint", struct.pack("<Q", GAME_ID)], program)
+    players = (1500 << 6 | 1 << 26) | (1500 << 6 | 1 << 26 | 5) << 64
+    properties = sum(7 << (i * 4) for i in range(28))
+    meta = 2 << 2 | 1 << 18 | 2 << 34  # current player 0, turn 2, round 1, 2 alive
+
+    data = discriminator("GameCheckpoint")
+    data += struct.pack("<QQ", GAME_ID, 1)
+    for value in (players, properties, meta):
+        data += value.to_bytes(16, "little")
+    data += bytes([bump])
+    assert len(data) == 73
+    dump(address, data, rent(len(data)))
+
+
+def platform(program: bytes) -> None:
+    """Baseline `PlatformConfig`: open games in a fixed array of 20 ids."""
+    address, bump = find_pda([b"platform"], program)
+    open_games = [GAME_ID]
+
+    data = discriminator("PlatformConfig")
+    data += pubkey_from_seed(4) + pubkey_from_seed(5) + pubkey_from_seed(6)  # owner, gm, fee addr
+    data += struct.pack("<Q", 0)  # game_count
+    data += struct.pack(f"<{MAX_OPEN_GAMES}Q", *open_games, *[0] * (MAX_OPEN_GAMES - len(open_games)))
+    data += struct.pack("<BB", len(open_games), bump)
+    assert len(data) == 274
+    dump(address, data, rent(len(data)))
+
+
+def main() -> None:
+    program = b58decode_key(PROGRAM_ID)
+    if sys.argv[1:] == ["checkpoint"]:
+        checkpoint(program)
+        return
+    if sys.argv[1:] == ["platform"]:
+        platform(program)
+        return
+    game, bump = find_pda([b"game", struct.pack("<Q", GAME_ID)], program)
+    deposited = [pubkey_from_seed(1), pubkey_from_seed(2)]
+    zero = bytes(32)
+    empty = SEATS - len(deposited)
+
+    data = discriminator("GameState")
+    data += struct.pack("<QB", GAME_ID, OPEN)
+    data += b"".join(deposited) + zero * empty  # players
+    data += b"".join(bytes([i + 1]) * 32 for i in range(len(deposited))) + zero * empty  # commits
+    data += zero * SEATS  # revealed_secrets
+    data += struct.pack("<BB", len(deposited), 0)  # deposit_count, reveal_count
+    data += zero + zero + zero  # dice_seed, winner, game_log_hash
+    data += struct.pack("<qqq", 0, 0, 0)  # reveal_deadline, created_at, started_at
+    data += struct.pack("<BB", 0, bump)  # winner_paid, bump
+    assert len(data) == 525
+    dump(game, data, rent(len(data)) + len(deposited) * ENTRY_FEE)
+
+
+if __name__ == "__main__":
+    main()
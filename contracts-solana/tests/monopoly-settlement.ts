@@ -8,7 +8,16 @@ import {
   LAMPORTS_PER_SOL,
   PublicKey,
 } from "@solana/web3.js";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
 import { keccak_256 } from "js-sha3";
+import { SolanaSettlementClient } from "../../packages/sdk/src/SolanaSettlementClient";
 
 const ENTRY_FEE = 10_000_000; // 0.01 SOL
 
@@ -27,9 +36,12 @@ describe("monopoly-settlement", () => {
   const gm = Keypair.generate();
   const platformFee = Keypair.generate();
   const players = Array.from({ length: 4 }, () => Keypair.generate());
+  // Open game listed by the baseline platform in tests/fixtures/legacy-platform.json
+  const legacyId = 1_000_000;
   const secrets = players.map(() => Keypair.generate().secretKey.slice(0, 32));
 
   let platformPda: PublicKey;
+  let treasuryPda: PublicKey;
 
   function gamePda(gameId: number): [PublicKey, number] {
     const buf = Buffer.alloc(8);
@@ -40,6 +52,40 @@ describe("monopoly-settlement", () => {
     );
   }
 
+  function lobbyPda(page: number): [PublicKey, number] {
+    const buf = Buffer.alloc(4);
+    buf.writeUInt32LE(page);
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("lobby"), buf],
+      program.programId
+    );
+  }
+
+  function vaultPda(gameId: number): [PublicKey, number] {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(gameId));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), buf],
+      program.programId
+    );
+  }
+
+  function treasuryVaultPda(mint: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), mint.toBuffer()],
+      program.programId
+    );
+  }
+
+  function clawPda(gameId: number, player: PublicKey): [PublicKey, number] {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(gameId));
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("claw"), buf, player.toBuffer()],
+      program.programId
+    );
+  }
+
   function checkpointPda(gameId: number): [PublicKey, number] {
     const buf = Buffer.alloc(8);
     buf.writeBigUInt64LE(BigInt(gameId));
@@ -49,11 +95,68 @@ describe("monopoly-settlement", () => {
     );
   }
 
+  // keccak(game_id (u64 LE) ‖ player ‖ secret), the program's COMMIT_SCHEME 1
+  function commitHash(gameId: number, player: PublicKey, secret: Uint8Array): number[] {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(gameId));
+    return Array.from(
+      keccakHash(Buffer.concat([buf, player.toBuffer(), Buffer.from(secret)]))
+    );
+  }
+
+  // Engine checkpoint packing, as in MonopolyEngine.packForCheckpoint
+  function packPlayer(position: number, cash: number, alive = true): anchor.BN {
+    return new anchor.BN(position)
+      .or(new anchor.BN(cash).shln(6))
+      .or(new anchor.BN(alive ? 1 : 0).shln(26));
+  }
+
+  function packProperties(owners: Record<number, number> = {}): anchor.BN {
+    let packed = new anchor.BN(0);
+    for (let i = 0; i < 28; i++) {
+      packed = packed.or(new anchor.BN(owners[i] ?? 7).shln(i * 4));
+    }
+    return packed;
+  }
+
+  function packMeta(current: number, turn: number, round: number, alive: number): anchor.BN {
+    return new anchor.BN(current)
+      .or(new anchor.BN(turn).shln(2))
+      .or(new anchor.BN(round).shln(18))
+      .or(new anchor.BN(alive).shln(34));
+  }
+
+  function writeCheckpoint(
+    round: number,
+    slots: anchor.BN[],
+    properties: anchor.BN,
+    meta: anchor.BN,
+    houses: number[] = Array(28).fill(0),
+    gameId = 0
+  ) {
+    const playersPacked = Array.from({ length: 8 }, (_, i) => slots[i] ?? new anchor.BN(0));
+    return program.methods
+      .writeCheckpoint(new anchor.BN(round), playersPacked, properties, meta, houses)
+      .accounts({
+        platform: platformPda,
+        game: gamePda(gameId)[0],
+        checkpoint: checkpointPda(gameId)[0],
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+  }
+
   before(async () => {
     [platformPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("platform")],
       program.programId
     );
+    [treasuryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      program.programId
+    );
 
     for (const kp of [gm, platformFee, ...players]) {
       const sig = await provider.connection.requestAirdrop(
@@ -64,31 +167,102 @@ describe("monopoly-settlement", () => {
     }
   });
 
-  it("initializes the platform", async () => {
+  it("upgrades the baseline platform, moving its open games to lobby page 0", async () => {
+    // Preloaded from tests/fixtures/legacy-platform.json in the layout first deployed, which
+    // kept open game ids in a fixed array. The suite takes it over instead of initializing.
+    const legacyOwner = Keypair.fromSeed(new Uint8Array(32).fill(4));
+    const sig = await provider.connection.requestAirdrop(legacyOwner.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(sig);
+    expect((await provider.connection.getAccountInfo(platformPda))!.data.length).to.equal(274);
+
+    try {
+      await program.methods
+        .migratePlatform()
+        .accounts({
+          platform: platformPda,
+          lobbyPage: null,
+          owner: legacyOwner.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([legacyOwner])
+        .rpc();
+      expect.fail("the baseline open games need a lobby page");
+    } catch (err: any) {
+      expect(err.toString()).to.include("MissingLobbyPage");
+    }
     await program.methods
-      .initialize(gm.publicKey, platformFee.publicKey)
+      .migratePlatform()
       .accounts({
         platform: platformPda,
-        owner: owner.publicKey,
+        lobbyPage: lobbyPda(0)[0],
+        owner: legacyOwner.publicKey,
         systemProgram: SystemProgram.programId,
       })
+      .signers([legacyOwner])
       .rpc();
 
-    const platform = await program.account.platformConfig.fetch(platformPda);
+    let platform = await program.account.platformConfig.fetch(platformPda);
+    expect(platform.owner.toString()).to.equal(legacyOwner.publicKey.toString());
+    expect(platform.bump).to.equal(
+      PublicKey.findProgramAddressSync([Buffer.from("platform")], program.programId)[1]
+    );
+    expect(platform.version).to.equal(2);
+    expect(platform.gameCount.toNumber()).to.equal(0);
+    expect(platform.openGameCount).to.equal(1);
+    expect(platform.lobbyPageCount).to.equal(1);
+    expect(platform.defaultTimeouts.reveal.toNumber()).to.equal(120);
+    expect(platform.defaultTimeouts.deposit.toNumber()).to.equal(600);
+    expect(platform.maxTimeouts.game.toNumber()).to.equal(86400);
+    expect(platform.platformFeeBps).to.equal(2000);
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.deep.equal([legacyId]);
+    expect(lobby.version).to.equal(1);
+
+    await program.methods
+      .proposeOwner(owner.publicKey)
+      .accounts({ platform: platformPda, owner: legacyOwner.publicKey })
+      .signers([legacyOwner])
+      .rpc();
+    await program.methods
+      .acceptOwner()
+      .accounts({ platform: platformPda, newOwner: owner.publicKey })
+      .rpc();
+    await program.methods
+      .setGmSigner(gm.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    await program.methods
+      .setPlatformFeeAddr(platformFee.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    platform = await program.account.platformConfig.fetch(platformPda);
     expect(platform.owner.toString()).to.equal(owner.publicKey.toString());
     expect(platform.gmSigner.toString()).to.equal(gm.publicKey.toString());
-    expect(platform.gameCount.toNumber()).to.equal(0);
-    expect(platform.openGameCount).to.equal(0);
+
+    await program.methods
+      .initTreasury()
+      .accounts({
+        platform: platformPda,
+        treasury: treasuryPda,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const treasury = await program.account.treasury.fetch(treasuryPda);
+    expect(treasury.version).to.equal(1);
   });
 
   it("creates an open game (GM only)", async () => {
     const [gameAddr] = gamePda(0);
 
     await program.methods
-      .createOpenGame()
+      .createOpenGame(new anchor.BN(ENTRY_FEE), 4, null, null, [6000, 2500, 1500], 0)
       .accounts({
         platform: platformPda,
         game: gameAddr,
+        lobbyPage: lobbyPda(0)[0],
         gm: gm.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -97,10 +271,18 @@ describe("monopoly-settlement", () => {
 
     const platform = await program.account.platformConfig.fetch(platformPda);
     expect(platform.gameCount.toNumber()).to.equal(1);
-    expect(platform.openGameCount).to.equal(1);
+    expect(platform.openGameCount).to.equal(2);
+    expect(platform.lobbyPageCount).to.equal(1);
+
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.deep.equal([legacyId, 0]);
 
     const game = await program.account.gameState.fetch(gameAddr);
     expect(game.gameId.toNumber()).to.equal(0);
+    expect(game.entryFee.toNumber()).to.equal(ENTRY_FEE);
+    expect(game.platformFeeBps).to.equal(2000);
+    expect(game.maxPlayers).to.equal(4);
+    expect(game.players.length).to.equal(4);
     expect(JSON.stringify(game.status)).to.include("open");
   });
 
@@ -108,13 +290,14 @@ describe("monopoly-settlement", () => {
     const [gameAddr] = gamePda(0);
 
     for (let i = 0; i < 4; i++) {
-      const secretHash = Array.from(keccakHash(new Uint8Array(secrets[i])));
+      const secretHash = commitHash(0, players[i].publicKey, secrets[i]);
 
       await program.methods
         .depositAndCommit(secretHash)
         .accounts({
           game: gameAddr,
           platform: platformPda,
+          lobbyPage: lobbyPda(0)[0],
           player: players[i].publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -127,7 +310,10 @@ describe("monopoly-settlement", () => {
     expect(JSON.stringify(game.status)).to.include("revealing");
 
     const platform = await program.account.platformConfig.fetch(platformPda);
-    expect(platform.openGameCount).to.equal(0);
+    expect(platform.openGameCount).to.equal(1);
+
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.deep.equal([legacyId]);
   });
 
   it("four players reveal seeds", async () => {
@@ -159,49 +345,260 @@ describe("monopoly-settlement", () => {
     );
   });
 
+  it("derives and verifies dice from the dice seed", async () => {
+    const [gameAddr] = gamePda(0);
+    const game = await program.account.gameState.fetch(gameAddr);
+
+    // DiceDeriver.roll: keccak256(seed ‖ uint256(turn)), dice from the first two hash bytes
+    const expected = [0, 1, 2, 3, 4].map((turn) => {
+      const word = Buffer.alloc(32);
+      word.writeBigUInt64BE(BigInt(turn), 24);
+      const hash = keccakHash(Buffer.concat([Buffer.from(game.diceSeed), word]));
+      const n = (hash[0] << 8) | hash[1];
+      return { d1: (n % 6) + 1, d2: (Math.floor(n / 6) % 6) + 1 };
+    });
+
+    const roll = await program.methods
+      .deriveDice(new anchor.BN(3))
+      .accounts({ game: gameAddr })
+      .view();
+    expect(roll).to.deep.equal(expected[3]);
+
+    await program.methods
+      .verifyRolls(new anchor.BN(0), expected)
+      .accounts({ game: gameAddr })
+      .rpc();
+
+    const forged = [...expected.slice(0, 4), { d1: 6, d2: 6 }];
+    if (expected[4].d1 === 6 && expected[4].d2 === 6) forged[4] = { d1: 1, d2: 1 };
+    try {
+      await program.methods
+        .verifyRolls(new anchor.BN(0), forged)
+        .accounts({ game: gameAddr })
+        .rpc();
+      expect.fail("a forged roll should not verify");
+    } catch (err: any) {
+      expect(err.toString()).to.include("RollMismatch");
+    }
+  });
+
   it("GM writes a checkpoint", async () => {
+    const [cpAddr] = checkpointPda(0);
+    const slots = [
+      packPlayer(5, 1000),
+      packPlayer(12, 950),
+      packPlayer(0, 1000),
+      packPlayer(39, 1200),
+    ];
+    const houses = Array.from({ length: 28 }, (_, i) => (i === 1 ? 3 : 0));
+
+    await writeCheckpoint(1, slots, packProperties({ 1: 0 }), packMeta(0, 4, 1, 4), houses);
+
+    const cp = await program.account.gameCheckpoint.fetch(cpAddr);
+    expect(cp.count.toNumber()).to.equal(1);
+    const entry = cp.history[0];
+    expect(entry.round.toNumber()).to.equal(1);
+    expect(entry.version).to.equal(3);
+    // every seat survives, not just the first two
+    expect(entry.playersPacked[3].eq(slots[3])).to.equal(true);
+    expect(entry.houses[1]).to.equal(3);
+  });
+
+  it("keeps checkpoint history and rejects rounds that do not advance", async () => {
+    const [cpAddr] = checkpointPda(0);
+    const write = (round: number) =>
+      writeCheckpoint(
+        round,
+        [0, 1, 2, 3].map((i) => packPlayer(i, 1000)),
+        packProperties(),
+        packMeta(0, round * 4, round, 4)
+      );
+
+    await write(2);
+    for (const stale of [2, 1]) {
+      try {
+        await write(stale);
+        expect.fail(`round ${stale} should be rejected after round 2`);
+      } catch (err: any) {
+        expect(err.toString()).to.include("StaleCheckpoint");
+      }
+    }
+
+    const cp = await program.account.gameCheckpoint.fetch(cpAddr);
+    expect(cp.count.toNumber()).to.equal(2);
+    expect(cp.history[0].round.toNumber()).to.equal(1);
+    expect(cp.history[1].round.toNumber()).to.equal(2);
+  });
+
+  it("rejects checkpoints the engine could not have produced", async () => {
+    const alive = [0, 1, 2, 3].map((i) => packPlayer(i, 1000));
+    const rejects = async (
+      label: string,
+      error: string,
+      write: () => Promise<string>
+    ) => {
+      try {
+        await write();
+        expect.fail(label);
+      } catch (err: any) {
+        expect(err.toString()).to.include(error);
+      }
+    };
+
+    await rejects("meta round must match", "InvalidCheckpointState", () =>
+      writeCheckpoint(3, alive, packProperties(), packMeta(0, 12, 4, 4))
+    );
+    await rejects("owner must be a seated player", "InvalidCheckpointState", () =>
+      writeCheckpoint(3, alive, packProperties({ 0: 5 }), packMeta(0, 12, 3, 4))
+    );
+
+    // player 3 goes bankrupt, then tries to come back
+    const bankrupt = [...alive.slice(0, 3), packPlayer(3, 0, false)];
+    await writeCheckpoint(3, bankrupt, packProperties(), packMeta(0, 12, 3, 3));
+    await rejects("dead player cannot revive", "InvalidCheckpointTransition", () =>
+      writeCheckpoint(4, alive, packProperties(), packMeta(0, 16, 4, 4))
+    );
+  });
+
+  it("migrate_checkpoint leaves a current-layout checkpoint untouched", async () => {
     const [gameAddr] = gamePda(0);
     const [cpAddr] = checkpointPda(0);
+    const before = await provider.connection.getAccountInfo(cpAddr);
 
     await program.methods
-      .writeCheckpoint(
-        new anchor.BN(1),
-        new anchor.BN(12345),
-        new anchor.BN(67890),
-        new anchor.BN(11111)
-      )
+      .migrateCheckpoint()
       .accounts({
-        platform: platformPda,
         game: gameAddr,
         checkpoint: cpAddr,
-        gm: gm.publicKey,
+        payer: gm.publicKey,
         systemProgram: SystemProgram.programId,
       })
       .signers([gm])
       .rpc();
 
-    const cp = await program.account.gameCheckpoint.fetch(cpAddr);
-    expect(cp.round.toNumber()).to.equal(1);
+    const after = await provider.connection.getAccountInfo(cpAddr);
+    expect(after!.data.equals(before!.data)).to.equal(true);
   });
 
-  it("GM settles the game", async () => {
+  it("GM settles the game once the committee threshold signs", async () => {
     const [gameAddr] = gamePda(0);
     const winnerKey = players[0].publicKey;
+    const ranking = players.map((p) => p.publicKey);
     const logHash = Array.from(new Uint8Array(32).fill(0xab));
+    const cosigners = [Keypair.generate(), Keypair.generate()];
+    const settle = (signers: Keypair[]) =>
+      program.methods
+        .settleGame(ranking, logHash)
+        .accounts({
+          platform: platformPda,
+          game: gameAddr,
+          gm: gm.publicKey,
+        })
+        .remainingAccounts(
+          signers.map((kp) => ({ pubkey: kp.publicKey, isSigner: true, isWritable: false }))
+        )
+        .signers([gm, ...signers])
+        .rpc();
 
     await program.methods
-      .settleGame(winnerKey, logHash)
-      .accounts({
-        platform: platformPda,
-        game: gameAddr,
-        gm: gm.publicKey,
-      })
-      .signers([gm])
+      .setGmCommittee([gm.publicKey, ...cosigners.map((kp) => kp.publicKey)], 2)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    try {
+      await settle([]);
+      expect.fail("the GM alone is below the threshold");
+    } catch (err: any) {
+      expect(err.toString()).to.include("ThresholdNotMet");
+    }
+
+    await settle([cosigners[1]]);
+
+    // Later tables settle with the GM alone
+    await program.methods
+      .setGmCommittee([], 0)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
       .rpc();
 
     const game = await program.account.gameState.fetch(gameAddr);
-    expect(JSON.stringify(game.status)).to.include("settled");
+    expect(JSON.stringify(game.status)).to.include("pendingSettlement");
     expect(game.winner.toString()).to.equal(winnerKey.toString());
+    expect(game.challengeDeadline.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("freezes payouts while a disputed settlement awaits the arbiter", async () => {
+    const [gameAddr] = gamePda(0);
+    const arbiter = Keypair.generate();
+    const withdraw = () =>
+      program.methods
+        .withdraw()
+        .accounts({
+          game: gameAddr,
+          winner: players[0].publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([players[0]])
+        .rpc();
+
+    try {
+      await withdraw();
+      expect.fail("payouts wait for the challenge period");
+    } catch (err: any) {
+      expect(err.toString()).to.include("ChallengePeriodActive");
+    }
+
+    try {
+      await program.methods
+        .disputeSettlement()
+        .accounts({ game: gameAddr, player: gm.publicKey })
+        .signers([gm])
+        .rpc();
+      expect.fail("only seated players can dispute");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotAPlayer");
+    }
+
+    await program.methods
+      .disputeSettlement()
+      .accounts({ game: gameAddr, player: players[3].publicKey })
+      .signers([players[3]])
+      .rpc();
+
+    let game = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(game.status)).to.include("disputed");
+    expect(game.disputedBy.toString()).to.equal(players[3].publicKey.toString());
+
+    try {
+      await withdraw();
+      expect.fail("a dispute freezes payouts");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidGameStatus");
+    }
+
+    await program.methods
+      .setArbiter(arbiter.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .resolveDispute({ confirm: {} })
+        .accounts({ platform: platformPda, game: gameAddr, arbiter: players[3].publicKey })
+        .signers([players[3]])
+        .rpc();
+      expect.fail("players cannot resolve their own dispute");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotArbiter");
+    }
+
+    await program.methods
+      .resolveDispute({ confirm: {} })
+      .accounts({ platform: platformPda, game: gameAddr, arbiter: arbiter.publicKey })
+      .signers([arbiter])
+      .rpc();
+
+    game = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(game.status)).to.include("settled");
   });
 
   it("winner withdraws", async () => {
@@ -216,8 +613,7 @@ describe("monopoly-settlement", () => {
       .accounts({
         game: gameAddr,
         winner: players[0].publicKey,
-        platformFeeAccount: platformFee.publicKey,
-        platform: platformPda,
+        treasury: treasuryPda,
       })
       .signers([players[0]])
       .rpc();
@@ -225,21 +621,161 @@ describe("monopoly-settlement", () => {
     const balAfter = await provider.connection.getBalance(
       players[0].publicKey
     );
-    const winnerShare = (ENTRY_FEE * 4 * 8000) / 10000;
+    const winnerShare = (ENTRY_FEE * 4 * 8000 * 6000) / 10000 / 10000;
     expect(balAfter - balBefore).to.be.greaterThan(winnerShare - 100000);
 
     const game = await program.account.gameState.fetch(gameAddr);
-    expect(game.winnerPaid).to.equal(true);
+    expect(game.paid[0]).to.equal(true);
+    expect(game.platformPaid).to.equal(true);
+  });
+
+  it("podium places claim their own shares", async () => {
+    const [gameAddr] = gamePda(0);
+
+    for (const place of [1, 2]) {
+      await program.methods
+        .withdraw()
+        .accounts({
+          game: gameAddr,
+          winner: players[place].publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([players[place]])
+        .rpc();
+    }
+
+    try {
+      await program.methods
+        .withdraw()
+        .accounts({
+          game: gameAddr,
+          winner: players[3].publicKey,
+          treasury: treasuryPda,
+        })
+        .signers([players[3]])
+        .rpc();
+      expect.fail("fourth place has no payout");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotWinner");
+    }
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    expect(game.paid.slice(0, 3)).to.deep.equal([true, true, true]);
+    expect(game.paid[3]).to.equal(false);
+  });
+
+  it("owner withdraws the rake from the treasury", async () => {
+    const rake = (ENTRY_FEE * 4 * 2000) / 10000;
+    const before = await provider.connection.getBalance(platformFee.publicKey);
+
+    try {
+      await program.methods
+        .withdrawPlatformFees()
+        .accounts({
+          platform: platformPda,
+          owner: players[0].publicKey,
+          treasury: treasuryPda,
+          platformFeeAccount: platformFee.publicKey,
+        })
+        .signers([players[0]])
+        .rpc();
+      expect.fail("only the owner can withdraw fees");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotOwner");
+    }
+
+    await program.methods
+      .withdrawPlatformFees()
+      .accounts({
+        platform: platformPda,
+        owner: owner.publicKey,
+        treasury: treasuryPda,
+        platformFeeAccount: platformFee.publicKey,
+      })
+      .rpc();
+
+    const after = await provider.connection.getBalance(platformFee.publicKey);
+    expect(after - before).to.equal(rake);
+  });
+
+  it("owner sets timeout defaults and bounds", async () => {
+    const timeouts = (reveal: number, deposit: number, game: number, challenge: number) => ({
+      reveal: new anchor.BN(reveal),
+      deposit: new anchor.BN(deposit),
+      game: new anchor.BN(game),
+      challenge: new anchor.BN(challenge),
+    });
+
+    await program.methods
+      .setTimeouts(
+        timeouts(120, 600, 86400, 3600),
+        timeouts(30, 1, 600, 1),
+        timeouts(600, 3600, 7 * 86400, 7 * 86400)
+      )
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    const platform = await program.account.platformConfig.fetch(platformPda);
+    expect(platform.minTimeouts.deposit.toNumber()).to.equal(1);
+    expect(platform.maxTimeouts.game.toNumber()).to.equal(7 * 86400);
+
+    // A bound near i64::MAX would overflow the deadlines computed from it
+    try {
+      await program.methods
+        .setTimeouts(
+          timeouts(120, 600, 86400, 3600),
+          timeouts(30, 1, 600, 1),
+          {
+            ...timeouts(600, 3600, 7 * 86400, 7 * 86400),
+            game: new anchor.BN("9223372036854775807"),
+          }
+        )
+        .accounts({ platform: platformPda, owner: owner.publicKey })
+        .rpc();
+      expect.fail("timeouts are capped at a year");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidTimeouts");
+    }
+  });
+
+  it("owner updates the platform fee within the cap", async () => {
+    try {
+      await program.methods
+        .setPlatformFeeBps(5000)
+        .accounts({ platform: platformPda, owner: owner.publicKey })
+        .rpc();
+      expect.fail("fee above the cap should be rejected");
+    } catch (err: any) {
+      expect(err.toString()).to.include("FeeTooHigh");
+    }
+
+    await program.methods
+      .setPlatformFeeBps(1500)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    const platform = await program.account.platformConfig.fetch(platformPda);
+    expect(platform.platformFeeBps).to.equal(1500);
+
+    // Game 0 keeps the split it was created with
+    const game = await program.account.gameState.fetch(gamePda(0)[0]);
+    expect(game.platformFeeBps).to.equal(2000);
   });
 
   it("creates and cancels a game after timeout", async () => {
     const [gameAddr] = gamePda(1);
 
     await program.methods
-      .createOpenGame()
+      .createOpenGame(new anchor.BN(ENTRY_FEE * 10), 2, null, {
+        reveal: new anchor.BN(60),
+        deposit: new anchor.BN(1),
+        game: new anchor.BN(3600),
+        challenge: new anchor.BN(3600),
+      }, [10000], 0)
       .accounts({
         platform: platformPda,
         game: gameAddr,
+        lobbyPage: lobbyPda(0)[0],
         gm: gm.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -249,6 +785,1199 @@ describe("monopoly-settlement", () => {
     const platformBefore = await program.account.platformConfig.fetch(
       platformPda
     );
-    expect(platformBefore.openGameCount).to.equal(1);
+    expect(platformBefore.openGameCount).to.equal(2);
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    expect(game.entryFee.toNumber()).to.equal(ENTRY_FEE * 10);
+    expect(game.players.length).to.equal(2);
+    expect(game.timeouts.deposit.toNumber()).to.equal(1);
+    expect(game.depositDeadline.toNumber()).to.equal(
+      game.createdAt.toNumber() + 1
+    );
+  });
+
+  it("token game holds deposits in the game vault", async () => {
+    const [gameAddr] = gamePda(2);
+    const [vaultAddr] = vaultPda(2);
+    const payer = (owner as anchor.Wallet).payer;
+
+    const mint = await createMint(
+      provider.connection,
+      payer,
+      owner.publicKey,
+      null,
+      6,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    await program.methods
+      .createOpenGame(new anchor.BN(5_000_000), 2, null, null, [10000], 0)
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        lobbyPage: lobbyPda(0)[0],
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+        mint,
+        vault: vaultAddr,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        treasuryVault: treasuryVaultPda(mint)[0],
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    expect(game.mint.toString()).to.equal(mint.toString());
+
+    for (let i = 0; i < 2; i++) {
+      const ata = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        players[i].publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        ata.address,
+        payer,
+        5_000_000,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const secretHash = commitHash(2, players[i].publicKey, secrets[i]);
+      await program.methods
+        .depositAndCommit(secretHash)
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: lobbyPda(0)[0],
+          player: players[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: ata.address,
+          vault: vaultAddr,
+          mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .signers([players[i]])
+        .rpc();
+    }
+
+    const vault = await getAccount(
+      provider.connection,
+      vaultAddr,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(Number(vault.amount)).to.equal(10_000_000);
+    expect(vault.owner.toString()).to.equal(gameAddr.toString());
+
+    const started = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(started.status)).to.include("revealing");
+  });
+
+  it("mints starting CLAW at game start and reclaims it after settlement", async () => {
+    const [clawMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claw_mint")],
+      program.programId
+    );
+    await program.methods
+      .initClawMint()
+      .accounts({
+        platform: platformPda,
+        clawMint,
+        owner: owner.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const [gameAddr] = gamePda(3);
+    await program.methods
+      .createOpenGame(new anchor.BN(ENTRY_FEE), 2, null, null, [10000], 0)
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        lobbyPage: lobbyPda(0)[0],
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const seated = [players[2], players[3]];
+    const clawAccounts = seated.map((p) => clawPda(3, p.publicKey)[0]);
+    for (let i = 0; i < 2; i++) {
+      const secretHash = commitHash(3, seated[i].publicKey, secrets[i]);
+      await program.methods
+        .depositAndCommit(secretHash)
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: lobbyPda(0)[0],
+          player: seated[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint,
+          playerClaw: clawAccounts[i],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([seated[i]])
+        .rpc();
+    }
+
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .revealSeed(Array.from(secrets[i]))
+        .accounts({
+          game: gameAddr,
+          player: seated[i].publicKey,
+          clawMint,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          clawAccounts.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          }))
+        )
+        .signers([seated[i]])
+        .rpc();
+    }
+
+    for (const addr of clawAccounts) {
+      const claw = await getAccount(provider.connection, addr);
+      expect(Number(claw.amount)).to.equal(1_000_000_000);
+    }
+    const started = await program.account.gameState.fetch(gameAddr);
+    expect(Buffer.from(started.clawBumps)).to.deep.equal(
+      Buffer.from(seated.map((p) => clawPda(3, p.publicKey)[1]))
+    );
+
+    // The engine always packs four seats, so a heads-up game has no checkpoint it could write
+    try {
+      await writeCheckpoint(
+        1,
+        [packPlayer(5, 1500), packPlayer(8, 1500)],
+        packProperties(),
+        packMeta(0, 2, 1, 2),
+        undefined,
+        3
+      );
+      expect.fail("a heads-up game cannot be checkpointed");
+    } catch (err: any) {
+      expect(err.toString()).to.include("CheckpointSeatsUnsupported");
+    }
+
+    await program.methods
+      .settleGame(
+        seated.map((p) => p.publicKey),
+        Array.from(new Uint8Array(32))
+      )
+      .accounts({ platform: platformPda, game: gameAddr, gm: gm.publicKey })
+      .signers([gm])
+      .rpc();
+
+    // Anyone can reclaim for a player; the agent SDK reclaims its own
+    await program.methods
+      .reclaimClaw()
+      .accounts({
+        game: gameAddr,
+        clawMint,
+        playerClaw: clawAccounts[0],
+        player: seated[0].publicKey,
+        caller: gm.publicKey,
+        clawTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([gm])
+      .rpc();
+    const agent = new SolanaSettlementClient(
+      provider.connection.rpcEndpoint,
+      program.programId.toBase58(),
+      JSON.stringify(Array.from(seated[1].secretKey))
+    );
+    await agent.reclaimClaw(3);
+
+    for (const addr of clawAccounts) {
+      const closed = await provider.connection.getAccountInfo(addr);
+      expect(closed).to.equal(null);
+    }
+    const reclaimed = await program.account.gameState.fetch(gameAddr);
+    expect(reclaimed.clawAccountsOpen).to.equal(0);
+  });
+
+  it("creates a fixed-roster game that only seated players can join", async () => {
+    const [gameAddr] = gamePda(4);
+    const seated = [players[0], players[1]];
+
+    await program.methods
+      .createGame(
+        seated.map((p) => p.publicKey),
+        new anchor.BN(ENTRY_FEE),
+        null,
+        null,
+        [10000]
+      )
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(game.status)).to.include("depositing");
+    expect(game.players.map((p) => p.toString())).to.deep.equal(
+      seated.map((p) => p.publicKey.toString())
+    );
+
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.not.include(4);
+
+    const [outsiderClaw] = clawPda(4, players[2].publicKey);
+    try {
+      await program.methods
+        .depositAndCommit(commitHash(4, players[2].publicKey, secrets[2]))
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: null,
+          player: players[2].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: outsiderClaw,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([players[2]])
+        .rpc();
+      expect.fail("unseated player should not be able to deposit");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotAPlayer");
+    }
+  });
+
+  it("depositors claim their own refunds from a cancelled game", async () => {
+    const [gameAddr] = gamePda(5);
+    const seated = [players[0], players[1]];
+
+    await program.methods
+      .createGame(
+        seated.map((p) => p.publicKey),
+        new anchor.BN(ENTRY_FEE),
+        null,
+        {
+          reveal: new anchor.BN(60),
+          deposit: new anchor.BN(2),
+          game: new anchor.BN(3600),
+          challenge: new anchor.BN(3600),
+        },
+        [10000]
+      )
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    await program.methods
+      .depositAndCommit(commitHash(5, players[0].publicKey, secrets[0]))
+      .accounts({
+        game: gameAddr,
+        platform: platformPda,
+        lobbyPage: null,
+        player: players[0].publicKey,
+        systemProgram: SystemProgram.programId,
+        playerTokenAccount: null,
+        vault: null,
+        mint: null,
+        tokenProgram: null,
+        clawMint: game.clawMint,
+        playerClaw: clawPda(5, players[0].publicKey)[0],
+        clawTokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([players[0]])
+      .rpc();
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await program.methods
+      .cancelGame()
+      .accounts({
+        game: gameAddr,
+        platform: platformPda,
+        lobbyPage: null,
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+
+    const claimRefund = (player: Keypair) =>
+      program.methods
+        .claimRefund()
+        .accounts({ game: gameAddr, player: player.publicKey })
+        .signers([player])
+        .rpc();
+
+    const balBefore = await provider.connection.getBalance(players[0].publicKey);
+    await claimRefund(players[0]);
+    const balAfter = await provider.connection.getBalance(players[0].publicKey);
+    expect(balAfter - balBefore).to.be.greaterThan(ENTRY_FEE - 100000);
+
+    try {
+      await claimRefund(players[0]);
+      expect.fail("a refund is claimed once");
+    } catch (err: any) {
+      expect(err.toString()).to.include("AlreadyPaid");
+    }
+
+    try {
+      await claimRefund(players[1]);
+      expect.fail("seats without a deposit have nothing to claim");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotAPlayer");
+    }
+
+    const refunded = await program.account.gameState.fetch(gameAddr);
+    expect(refunded.paid).to.deep.equal([true, false]);
+  });
+
+  it("a depositor leaves an open game and frees the seat", async () => {
+    const [gameAddr] = gamePda(6);
+
+    await program.methods
+      .createOpenGame(new anchor.BN(ENTRY_FEE), 2, null, null, [10000], 0)
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        lobbyPage: lobbyPda(0)[0],
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    const deposit = (i: number, hash = commitHash(6, players[i].publicKey, secrets[i])) =>
+      program.methods
+        .depositAndCommit(hash)
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: lobbyPda(0)[0],
+          player: players[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: clawPda(6, players[i].publicKey)[0],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([players[i]])
+        .rpc();
+    const leave = (i: number, claw = true) =>
+      program.methods
+        .leaveGame()
+        .accounts({
+          game: gameAddr,
+          player: players[i].publicKey,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          playerClaw: claw ? clawPda(6, players[i].publicKey)[0] : null,
+          clawTokenProgram: claw ? TOKEN_PROGRAM_ID : null,
+        })
+        .signers([players[i]])
+        .rpc();
+
+    await deposit(2);
+    try {
+      await program.methods
+        .leaveGame()
+        .accounts({
+          game: gameAddr,
+          player: players[2].publicKey,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          playerClaw: clawPda(6, players[2].publicKey)[0],
+          clawTokenProgram: null,
+        })
+        .signers([players[2]])
+        .rpc();
+      expect.fail("leaving a CLAW game must close the CLAW account");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidClawAccounts");
+    }
+
+    const balBefore = await provider.connection.getBalance(players[2].publicKey);
+    await leave(2);
+    const balAfter = await provider.connection.getBalance(players[2].publicKey);
+    expect(balAfter - balBefore).to.be.greaterThan(ENTRY_FEE - 100000);
+
+    let left = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(left.status)).to.include("open");
+    expect(left.depositCount).to.equal(0);
+    expect(left.players[0].toString()).to.equal(PublicKey.default.toString());
+    expect(left.clawAccountsOpen).to.equal(0);
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.include(6);
+
+    // Their CLAW account is already closed, so it is left out
+    try {
+      await leave(2, false);
+      expect.fail("a player who left has no seat");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotAPlayer");
+    }
+
+    // The seat can be taken again, by a newcomer or the same player. The newcomer copies the
+    // other player's commit, which cannot be opened from their own seat.
+    await deposit(3, commitHash(6, players[2].publicKey, secrets[2]));
+    await deposit(2);
+    left = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(left.status)).to.include("revealing");
+    expect(left.commitScheme).to.equal(1);
+
+    const reveal = (i: number, secret: Uint8Array) =>
+      program.methods
+        .revealSeed(Array.from(secret))
+        .accounts({
+          game: gameAddr,
+          player: players[i].publicKey,
+          clawMint: game.clawMint,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([players[i]])
+        .rpc();
+    await reveal(2, secrets[2]);
+    try {
+      await reveal(3, secrets[2]);
+      expect.fail("a copied commit does not open for another player");
+    } catch (err: any) {
+      expect(err.toString()).to.include("HashMismatch");
+    }
+  });
+
+  it("forfeits non-revealers' deposits to the revealers and the treasury", async () => {
+    const [gameAddr] = gamePda(7);
+    const seated = [players[0], players[1], players[2]];
+
+    try {
+      await program.methods
+        .setForfeitTreasuryBps(10001)
+        .accounts({ platform: platformPda, owner: owner.publicKey })
+        .rpc();
+      expect.fail("the treasury cannot take more than everything");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidForfeitSplit");
+    }
+    await program.methods
+      .setForfeitTreasuryBps(5000)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    const timeouts = (reveal: number, deposit: number, game: number, challenge: number) => ({
+      reveal: new anchor.BN(reveal),
+      deposit: new anchor.BN(deposit),
+      game: new anchor.BN(game),
+      challenge: new anchor.BN(challenge),
+    });
+    await program.methods
+      .setTimeouts(
+        timeouts(120, 600, 86400, 3600),
+        timeouts(1, 1, 600, 1),
+        timeouts(600, 3600, 7 * 86400, 7 * 86400)
+      )
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+
+    await program.methods
+      .createGame(
+        seated.map((p) => p.publicKey),
+        new anchor.BN(ENTRY_FEE),
+        null,
+        timeouts(2, 600, 3600, 3600),
+        [10000]
+      )
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    expect(game.forfeitTreasuryBps).to.equal(5000);
+    // The third player commits to the all-zero secret, which can never be revealed
+    const committed = [secrets[0], secrets[1], new Uint8Array(32)];
+    for (let i = 0; i < 3; i++) {
+      await program.methods
+        .depositAndCommit(commitHash(7, seated[i].publicKey, committed[i]))
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: null,
+          player: seated[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: clawPda(7, seated[i].publicKey)[0],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([seated[i]])
+        .rpc();
+    }
+    const reveal = (i: number, secret: Uint8Array) =>
+      program.methods
+        .revealSeed(Array.from(secret))
+        .accounts({
+          game: gameAddr,
+          player: seated[i].publicKey,
+          clawMint: game.clawMint,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([seated[i]])
+        .rpc();
+    for (let i = 0; i < 2; i++) {
+      await reveal(i, secrets[i]);
+    }
+    try {
+      await reveal(0, secrets[0]);
+      expect.fail("a secret is revealed once");
+    } catch (err: any) {
+      expect(err.toString()).to.include("AlreadyRevealed");
+    }
+    try {
+      await reveal(2, committed[2]);
+      expect.fail("the all-zero secret reads as unrevealed");
+    } catch (err: any) {
+      expect(err.toString()).to.include("EmptySecret");
+    }
+    expect((await program.account.gameState.fetch(gameAddr)).revealCount).to.equal(2);
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    const treasuryBefore = await provider.connection.getBalance(treasuryPda);
+    await program.methods
+      .voidGame()
+      .accounts({ game: gameAddr, caller: gm.publicKey, treasury: treasuryPda })
+      .signers([gm])
+      .rpc();
+    const treasuryAfter = await provider.connection.getBalance(treasuryPda);
+    expect(treasuryAfter - treasuryBefore).to.equal(ENTRY_FEE / 2);
+
+    const voided = await program.account.gameState.fetch(gameAddr);
+    expect(voided.forfeitedCount).to.equal(1);
+    expect(voided.forfeitShare.toNumber()).to.equal(ENTRY_FEE / 4);
+
+    const claimRefund = (player: Keypair) =>
+      program.methods
+        .claimRefund()
+        .accounts({ game: gameAddr, player: player.publicKey })
+        .signers([player])
+        .rpc();
+
+    try {
+      await claimRefund(seated[2]);
+      expect.fail("a non-revealer's deposit is forfeited");
+    } catch (err: any) {
+      expect(err.toString()).to.include("DepositForfeited");
+    }
+
+    const balBefore = await provider.connection.getBalance(seated[0].publicKey);
+    await claimRefund(seated[0]);
+    const balAfter = await provider.connection.getBalance(seated[0].publicKey);
+    expect(balAfter - balBefore).to.be.greaterThan(ENTRY_FEE + ENTRY_FEE / 4 - 100000);
+
+    await program.methods
+      .setForfeitTreasuryBps(0)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+  });
+
+  it("refuses checkpoints for more seats than the engine plays", async () => {
+    const [gameAddr] = gamePda(8);
+    const extra = Array.from({ length: 4 }, () => Keypair.generate());
+    for (const kp of extra) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+    const seated = [...players, ...extra];
+    const seatSecrets = seated.map(() => Keypair.generate().secretKey.slice(0, 32));
+
+    await program.methods
+      .createGame(
+        seated.map((p) => p.publicKey),
+        new anchor.BN(ENTRY_FEE),
+        null,
+        null,
+        [10000]
+      )
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    const clawAccounts = seated.map((p) => clawPda(8, p.publicKey)[0]);
+    for (let i = 0; i < 8; i++) {
+      await program.methods
+        .depositAndCommit(commitHash(8, seated[i].publicKey, seatSecrets[i]))
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: null,
+          player: seated[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: clawAccounts[i],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([seated[i]])
+        .rpc();
+    }
+    for (let i = 0; i < 8; i++) {
+      await program.methods
+        .revealSeed(Array.from(seatSecrets[i]))
+        .accounts({
+          game: gameAddr,
+          player: seated[i].publicKey,
+          clawMint: game.clawMint,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          clawAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([seated[i]])
+        .rpc();
+    }
+    expect(JSON.stringify((await program.account.gameState.fetch(gameAddr)).status)).to.include(
+      "started"
+    );
+
+    // Seat 7 could neither be the current player nor own a property in the 4-seat layout
+    const slots = Array.from({ length: 8 }, (_, i) => packPlayer(i, 1500));
+    try {
+      await writeCheckpoint(1, slots, packProperties(), packMeta(0, 8, 1, 8), undefined, 8);
+      expect.fail("an 8-seat game does not fit the checkpoint layout");
+    } catch (err: any) {
+      expect(err.toString()).to.include("CheckpointSeatsUnsupported");
+    }
+  });
+
+  it("voids a game nobody revealed in without the treasury account", async () => {
+    const [gameAddr] = gamePda(9);
+    const seated = [players[0], players[1]];
+
+    await program.methods
+      .createGame(
+        seated.map((p) => p.publicKey),
+        new anchor.BN(ENTRY_FEE),
+        null,
+        {
+          reveal: new anchor.BN(2),
+          deposit: new anchor.BN(600),
+          game: new anchor.BN(3600),
+          challenge: new anchor.BN(3600),
+        },
+        [10000]
+      )
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .depositAndCommit(commitHash(9, seated[i].publicKey, secrets[i]))
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: null,
+          player: seated[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: clawPda(9, seated[i].publicKey)[0],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([seated[i]])
+        .rpc();
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 3000));
+    await program.methods
+      .voidGame()
+      .accounts({ game: gameAddr, caller: gm.publicKey, treasury: null })
+      .signers([gm])
+      .rpc();
+
+    const voided = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(voided.status)).to.include("voided");
+    expect(voided.forfeitedCount).to.equal(0);
+    for (const player of seated) {
+      const before = await provider.connection.getBalance(player.publicKey);
+      await program.methods
+        .claimRefund()
+        .accounts({ game: gameAddr, player: player.publicKey })
+        .signers([player])
+        .rpc();
+      const after = await provider.connection.getBalance(player.publicKey);
+      expect(after - before).to.be.greaterThan(ENTRY_FEE - 100000);
+    }
+  });
+
+  it("ranks only depositors in a fixed-roster game started short", async () => {
+    const [gameAddr] = gamePda(10);
+    const seated = [players[0], players[1], players[2]];
+
+    await program.methods
+      .createGame(seated.map((p) => p.publicKey), new anchor.BN(ENTRY_FEE), 2, null, [10000])
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        gm: gm.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([gm])
+      .rpc();
+
+    const game = await program.account.gameState.fetch(gameAddr);
+    const depositors = seated.slice(0, 2);
+    const clawAccounts = depositors.map((p) => clawPda(10, p.publicKey)[0]);
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .depositAndCommit(commitHash(10, depositors[i].publicKey, secrets[i]))
+        .accounts({
+          game: gameAddr,
+          platform: platformPda,
+          lobbyPage: null,
+          player: depositors[i].publicKey,
+          systemProgram: SystemProgram.programId,
+          playerTokenAccount: null,
+          vault: null,
+          mint: null,
+          tokenProgram: null,
+          clawMint: game.clawMint,
+          playerClaw: clawAccounts[i],
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([depositors[i]])
+        .rpc();
+    }
+    await program.methods
+      .startReveal()
+      .accounts({ platform: platformPda, game: gameAddr, lobbyPage: null, gm: gm.publicKey })
+      .signers([gm])
+      .rpc();
+    for (let i = 0; i < 2; i++) {
+      await program.methods
+        .revealSeed(Array.from(secrets[i]))
+        .accounts({
+          game: gameAddr,
+          player: depositors[i].publicKey,
+          clawMint: game.clawMint,
+          clawTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          clawAccounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([depositors[i]])
+        .rpc();
+    }
+
+    const settle = (ranking: PublicKey[]) =>
+      program.methods
+        .settleGame(ranking, Array.from(new Uint8Array(32)))
+        .accounts({ platform: platformPda, game: gameAddr, gm: gm.publicKey })
+        .signers([gm])
+        .rpc();
+    try {
+      // The seated player who never paid in cannot take the winner's place
+      await settle([players[2].publicKey, players[0].publicKey]);
+      expect.fail("a seat without a deposit cannot be ranked");
+    } catch (err: any) {
+      expect(err.toString()).to.include("InvalidRanking");
+    }
+    await settle([players[1].publicKey, players[0].publicKey]);
+    const settled = await program.account.gameState.fetch(gameAddr);
+    expect(settled.winner.toString()).to.equal(players[1].publicKey.toString());
+  });
+
+  it("closes a fully paid game and its checkpoint, refunding rent to the GM", async () => {
+    const [gameAddr] = gamePda(0);
+    const [cpAddr] = checkpointPda(0);
+
+    try {
+      await program.methods
+        .closeGame()
+        .accounts({
+          game: gameAddr,
+          checkpoint: cpAddr,
+          rentPayer: gm.publicKey,
+          caller: gm.publicKey,
+        })
+        .signers([gm])
+        .rpc();
+      expect.fail("game cannot close while its checkpoint is open");
+    } catch (err: any) {
+      expect(err.toString()).to.include("CannotClose");
+    }
+
+    const balBefore = await provider.connection.getBalance(gm.publicKey);
+
+    await program.methods
+      .closeCheckpoint()
+      .accounts({
+        game: gameAddr,
+        checkpoint: cpAddr,
+        payer: gm.publicKey,
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+
+    await program.methods
+      .closeGame()
+      .accounts({
+        game: gameAddr,
+        checkpoint: cpAddr,
+        rentPayer: gm.publicKey,
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(cpAddr)).to.equal(null);
+    expect(await provider.connection.getAccountInfo(gameAddr)).to.equal(null);
+    const balAfter = await provider.connection.getBalance(gm.publicKey);
+    expect(balAfter).to.be.greaterThan(balBefore);
+  });
+
+  it("transfers ownership in two steps", async () => {
+    const newOwner = Keypair.generate();
+    const accept = (signer: Keypair) =>
+      program.methods
+        .acceptOwner()
+        .accounts({ platform: platformPda, newOwner: signer.publicKey })
+        .signers([signer])
+        .rpc();
+
+    await program.methods
+      .proposeOwner(newOwner.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    await program.methods
+      .cancelOwnerProposal()
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    try {
+      await accept(newOwner);
+      expect.fail("a cancelled proposal cannot be accepted");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotPendingOwner");
+    }
+
+    await program.methods
+      .proposeOwner(newOwner.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    try {
+      await accept(gm);
+      expect.fail("only the proposed key can accept");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotPendingOwner");
+    }
+    await accept(newOwner);
+
+    let platform = await program.account.platformConfig.fetch(platformPda);
+    expect(platform.owner.toString()).to.equal(newOwner.publicKey.toString());
+    expect(platform.pendingOwner.toString()).to.equal(PublicKey.default.toString());
+
+    // Hand ownership back to the provider wallet
+    await program.methods
+      .proposeOwner(owner.publicKey)
+      .accounts({ platform: platformPda, owner: newOwner.publicKey })
+      .signers([newOwner])
+      .rpc();
+    await program.methods
+      .acceptOwner()
+      .accounts({ platform: platformPda, newOwner: owner.publicKey })
+      .rpc();
+
+    platform = await program.account.platformConfig.fetch(platformPda);
+    expect(platform.owner.toString()).to.equal(owner.publicKey.toString());
+  });
+
+  it("pauses new games, deposits and settlement but keeps refunds open", async () => {
+    const guardian = Keypair.generate();
+    const setPaused = (paused: number, signer?: Keypair) =>
+      program.methods
+        .setPaused(paused)
+        .accounts({
+          platform: platformPda,
+          authority: signer ? signer.publicKey : owner.publicKey,
+        })
+        .signers(signer ? [signer] : [])
+        .rpc();
+
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accounts({ platform: platformPda, owner: owner.publicKey })
+      .rpc();
+    await setPaused(0b111, guardian);
+
+    const platform = await program.account.platformConfig.fetch(platformPda);
+    try {
+      await program.methods
+        .createOpenGame(new anchor.BN(ENTRY_FEE), 2, null, null, [10000], 0)
+        .accounts({
+          platform: platformPda,
+          game: gamePda(platform.gameCount.toNumber())[0],
+          lobbyPage: lobbyPda(0)[0],
+          gm: gm.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gm])
+        .rpc();
+      expect.fail("game creation is paused");
+    } catch (err: any) {
+      expect(err.toString()).to.include("Paused");
+    }
+
+    // Game 1's deposit window closed long ago; cancelling it still works while paused
+    await program.methods
+      .cancelGame()
+      .accounts({
+        game: gamePda(1)[0],
+        platform: platformPda,
+        lobbyPage: lobbyPda(0)[0],
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+    const cancelled = await program.account.gameState.fetch(gamePda(1)[0]);
+    expect(JSON.stringify(cancelled.status)).to.include("voided");
+
+    try {
+      await setPaused(0, guardian);
+      expect.fail("the guardian cannot lift a pause");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotGuardian");
+    }
+    await setPaused(0);
+  });
+
+  it("migrations stamp versions and leave current layouts in place", async () => {
+    const [gameAddr] = gamePda(3);
+    const before = await provider.connection.getAccountInfo(gameAddr);
+
+    await program.methods
+      .migratePlatform()
+      .accounts({
+        platform: platformPda,
+        lobbyPage: null,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .migrateGame(new anchor.BN(3))
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .migrateLobbyPage(0)
+      .accounts({
+        platform: platformPda,
+        lobbyPage: lobbyPda(0)[0],
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    try {
+      await program.methods
+        .migrateGame(new anchor.BN(3))
+        .accounts({
+          platform: platformPda,
+          game: gameAddr,
+          owner: gm.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([gm])
+        .rpc();
+      expect.fail("only the owner can migrate games");
+    } catch (err: any) {
+      expect(err.toString()).to.include("NotOwner");
+    }
+
+    const after = await provider.connection.getAccountInfo(gameAddr);
+    expect(after!.data.length).to.equal(before!.data.length);
+    expect((await program.account.platformConfig.fetch(platformPda)).version).to.equal(2);
+    expect((await program.account.gameState.fetch(gameAddr)).version).to.equal(4);
+    expect((await program.account.lobbyPage.fetch(lobbyPda(0)[0])).version).to.equal(1);
+  });
+
+  it("upgrades a baseline SOL game at the owner's expense, leaving its pot whole", async () => {
+    // Preloaded from tests/fixtures/legacy-game.json in the layout first deployed: four fixed
+    // seats, two of them deposited, still open in the lobby the platform upgrade filled.
+    // legacy-checkpoint.json holds its checkpoint in the v1 layout, which has no payer.
+    const [gameAddr] = gamePda(legacyId);
+    const seats = [1, 2].map((b) => Keypair.fromSeed(new Uint8Array(32).fill(b)));
+    // Baseline games were paid for by the GM
+    const rentPayer = gm.publicKey;
+    for (const kp of seats) {
+      const sig = await provider.connection.requestAirdrop(kp.publicKey, LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(sig);
+    }
+
+    const before = await provider.connection.getAccountInfo(gameAddr);
+    expect(before!.data.length).to.equal(525);
+    await program.methods
+      .migrateGame(new anchor.BN(legacyId))
+      .accounts({
+        platform: platformPda,
+        game: gameAddr,
+        owner: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const after = await provider.connection.getAccountInfo(gameAddr);
+    expect(after!.data.length).to.be.greaterThan(before!.data.length);
+    const extraRent =
+      (await provider.connection.getMinimumBalanceForRentExemption(after!.data.length)) -
+      (await provider.connection.getMinimumBalanceForRentExemption(before!.data.length));
+    expect(after!.lamports - before!.lamports).to.equal(extraRent);
+
+    let game = await program.account.gameState.fetch(gameAddr);
+    expect(game.version).to.equal(4);
+    expect(JSON.stringify(game.status)).to.include("open");
+    expect(game.players.map((p) => p.toString())).to.deep.equal([
+      ...seats.map((kp) => kp.publicKey.toString()),
+      PublicKey.default.toString(),
+      PublicKey.default.toString(),
+    ]);
+    expect(game.depositCount).to.equal(2);
+    expect(game.paid).to.deep.equal([false, false, false, false]);
+    expect(game.entryFee.toNumber()).to.equal(ENTRY_FEE);
+    expect(game.maxPlayers).to.equal(4);
+    expect(game.minPlayers).to.equal(4);
+    expect(game.depositDeadline.toNumber()).to.equal(600);
+    expect(game.payoutBps).to.deep.equal([10000]);
+    expect(game.lobbyPage).to.equal(0);
+    expect(game.rentPayer.toString()).to.equal(rentPayer.toString());
+    expect(game.commitScheme).to.equal(0);
+    expect(game.clawBumps.length).to.equal(0);
+
+    const [cpAddr] = checkpointPda(legacyId);
+    expect((await provider.connection.getAccountInfo(cpAddr))!.data.length).to.equal(73);
+    await program.methods
+      .migrateCheckpoint()
+      .accounts({
+        game: gameAddr,
+        checkpoint: cpAddr,
+        payer: owner.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    const cp = await program.account.gameCheckpoint.fetch(cpAddr);
+    expect(cp.gameId.toNumber()).to.equal(legacyId);
+    expect(cp.count.toNumber()).to.equal(1);
+    expect(cp.history[0].round.toNumber()).to.equal(1);
+    expect(cp.history[0].version).to.equal(1);
+    expect(cp.history[0].playersPacked[1].eq(packPlayer(5, 1500))).to.equal(true);
+    expect(cp.payer.toString()).to.equal(rentPayer.toString());
+
+    // Its deposit window closed long ago: cancelling takes it out of the lobby
+    await program.methods
+      .cancelGame()
+      .accounts({
+        game: gameAddr,
+        platform: platformPda,
+        lobbyPage: lobbyPda(0)[0],
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+    game = await program.account.gameState.fetch(gameAddr);
+    expect(JSON.stringify(game.status)).to.include("voided");
+    const lobby = await program.account.lobbyPage.fetch(lobbyPda(0)[0]);
+    expect(lobby.gameIds.map((id) => id.toNumber())).to.not.include(legacyId);
+
+    // Draining the pot leaves the account rent-exempt at its new size
+    for (const player of seats) {
+      await program.methods
+        .claimRefund()
+        .accounts({ game: gameAddr, player: player.publicKey })
+        .signers([player])
+        .rpc();
+    }
+    await program.methods
+      .closeCheckpoint()
+      .accounts({ game: gameAddr, checkpoint: cpAddr, payer: rentPayer, caller: gm.publicKey })
+      .signers([gm])
+      .rpc();
+    await program.methods
+      .closeGame()
+      .accounts({
+        game: gameAddr,
+        checkpoint: cpAddr,
+        rentPayer,
+        caller: gm.publicKey,
+      })
+      .signers([gm])
+      .rpc();
+    expect(await provider.connection.getAccountInfo(gameAddr)).to.equal(null);
   });
 });
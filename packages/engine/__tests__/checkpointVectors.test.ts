@@ -0,0 +1,59 @@
+import { describe, it, expect } from "vitest";
+import * as fs from "fs";
+import * as path from "path";
+import { MonopolyEngine } from "../src/MonopolyEngine";
+
+// Golden vectors shared with the Rust checkpoint codec (contracts-solana/crates/monopoly-checkpoint)
+const VECTORS_PATH = path.resolve(
+  __dirname, "../../../contracts-solana/crates/monopoly-checkpoint/vectors/checkpoint.json",
+);
+
+interface CheckpointVector {
+  name: string;
+  players: { position: number; cash: number; alive: boolean; inJail: boolean; jailTurns: number }[];
+  owners: number[];
+  mortgaged: boolean[];
+  meta: { currentPlayerIndex: number; currentTurn: number; currentRound: number; aliveCount: number };
+  playersPacked: string;
+  propertiesPacked: string;
+  metaPacked: string;
+}
+
+const SEED = "0x" + "ab".repeat(32);
+const PLAYERS: [string, string, string, string] = [
+  "0x1111111111111111111111111111111111111111",
+  "0x2222222222222222222222222222222222222222",
+  "0x3333333333333333333333333333333333333333",
+  "0x4444444444444444444444444444444444444444",
+];
+
+const vectors: CheckpointVector[] = JSON.parse(fs.readFileSync(VECTORS_PATH, "utf8"));
+
+describe("checkpoint golden vectors", () => {
+  for (const v of vectors) {
+    it(`packs "${v.name}" like the Rust codec`, () => {
+      const engine = new MonopolyEngine(PLAYERS, SEED);
+      v.players.forEach((p, i) => Object.assign(engine.state.players[i], p));
+      engine.state.properties.forEach((prop, i) => {
+        prop.owner = v.owners[i];
+        prop.mortgaged = v.mortgaged[i];
+      });
+      Object.assign(engine.state, v.meta);
+
+      const { playersPacked, propertiesPacked, metaPacked } = engine.packForCheckpoint();
+      expect(playersPacked).toBe(BigInt(v.playersPacked));
+      expect(propertiesPacked).toBe(BigInt(v.propertiesPacked));
+      expect(metaPacked).toBe(BigInt(v.metaPacked));
+    });
+
+    it(`unpacks "${v.name}" like the Rust codec`, () => {
+      const engine = MonopolyEngine.fromCheckpoint(
+        PLAYERS, SEED, BigInt(v.playersPacked), BigInt(v.propertiesPacked), BigInt(v.metaPacked),
+      );
+      v.players.forEach((p, i) => expect(engine.state.players[i]).toMatchObject(p));
+      expect(engine.state.properties.map(p => p.owner)).toEqual(v.owners);
+      expect(engine.state.properties.map(p => p.mortgaged)).toEqual(v.mortgaged);
+      expect(engine.state).toMatchObject(v.meta);
+    });
+  }
+});
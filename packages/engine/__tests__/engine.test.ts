@@ -601,12 +601,26 @@ describe("MonopolyEngine", () => {
       const { playersPacked, propertiesPacked, metaPacked } = engine.packForCheckpoint();
       const restored = MonopolyEngine.fromCheckpoint(PLAYERS, SEED, playersPacked, propertiesPacked, metaPacked);
 
-      // Houses should be 0 after recovery (not persisted in checkpoint)
+      // Houses should be 0 after recovery from a checkpoint without them (EVM)
       for (const prop of restored.state.properties) {
         expect(prop.houses).toBe(0);
       }
     });
 
+    it("recovery from checkpoint should restore houses when provided", () => {
+      const engine = setupMonopoly();
+      engine.executeAction({ type: "buildHouse", propertyIndex: 0 });
+      engine.executeAction({ type: "buildHouse", propertyIndex: 1 });
+
+      const { playersPacked, propertiesPacked, metaPacked, houses } = engine.packForCheckpoint();
+      const restored = MonopolyEngine.fromCheckpoint(
+        PLAYERS, SEED, playersPacked, propertiesPacked, metaPacked, houses,
+      );
+
+      expect(restored.state.properties[0].houses).toBe(1);
+      expect(restored.state.properties[1].houses).toBe(1);
+    });
+
     it("snapshot should include houses", () => {
       const engine = setupMonopoly();
       engine.executeAction({ type: "buildHouse", propertyIndex: 0 });
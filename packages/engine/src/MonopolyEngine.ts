@@ -330,12 +330,10 @@ export class MonopolyEngine {
 
   /**
    * Pack entire state into a compact form for on-chain checkpoint.
-   * Returns { playersPacked, propertiesPacked, metaPacked } as bigints.
-   *
-   * NOTE: Houses are NOT persisted in the checkpoint (no contract change in v1).
-   * Recovery from checkpoint resets all properties to 0 houses.
+   * Returns { playersPacked, propertiesPacked, metaPacked } as bigints, plus the house count per
+   * property. Only the Solana checkpoint (v2) stores houses; the EVM contract drops them.
    */
-  packForCheckpoint(): { playersPacked: bigint; propertiesPacked: bigint; metaPacked: bigint } {
+  packForCheckpoint(): { playersPacked: bigint; propertiesPacked: bigint; metaPacked: bigint; houses: number[] } {
     // playersPacked: 4 players * 64 bits each = 256 bits
     // Each player: position(6) + cash(20) + alive(1) + inJail(1) + jailTurns(2) = 30 bits (pad to 64)
     let playersPacked = 0n;
@@ -369,12 +367,14 @@ export class MonopolyEngine {
     metaPacked |= (BigInt(this.state.currentRound) & 0xFFFFn) << 18n;
     metaPacked |= (BigInt(this.state.aliveCount) & 0x7n) << 34n;
 
-    return { playersPacked, propertiesPacked, metaPacked };
+    const houses = this.state.properties.map(p => p.houses);
+
+    return { playersPacked, propertiesPacked, metaPacked, houses };
   }
 
   /**
    * Restore state from a checkpoint. Used for crash recovery.
-   * Houses are reset to 0 (not persisted in checkpoint).
+   * Houses are reset to 0 unless the checkpoint carried them.
    */
   static fromCheckpoint(
     addresses: [string, string, string, string],
@@ -382,6 +382,7 @@ export class MonopolyEngine {
     playersPacked: bigint,
     propertiesPacked: bigint,
     metaPacked: bigint,
+    houses?: number[],
   ): MonopolyEngine {
     const engine = new MonopolyEngine(addresses, diceSeed);
 
@@ -401,6 +402,7 @@ export class MonopolyEngine {
       const ownerBits = Number(slot & 0x7n);
       engine.state.properties[i].owner = ownerBits === 7 ? -1 : ownerBits;
       engine.state.properties[i].mortgaged = ((slot >> 3n) & 1n) === 1n;
+      engine.state.properties[i].houses = houses?.[i] ?? 0;
     }
 
     // Unpack meta
@@ -1113,23 +1115,28 @@ export class MonopolyEngine {
     this.emit({ type: "turnStarted", player: next, turn: this.state.currentTurn });
   }
 
+  /** Net worth: cash + unmortgaged property values + mortgaged value / 2 + house sell value. */
+  netWorth(playerIndex: number): number {
+    let worth = this.state.players[playerIndex].cash;
+    for (const prop of this.state.properties) {
+      if (prop.owner === playerIndex) {
+        const tile = PROPERTY_TILES[prop.index];
+        worth += prop.mortgaged ? Math.floor(tile.mortgageValue / 2) : tile.price;
+        // Add house value (sell at half cost)
+        if (prop.houses > 0 && tile.houseCost) {
+          worth += prop.houses * Math.floor(tile.houseCost / 2);
+        }
+      }
+    }
+    return worth;
+  }
+
   private endByMaxRounds(): void {
-    // Calculate net worth: cash + unmortgaged property values + mortgaged value / 2 + house sell value
     let bestPlayer = -1;
     let bestWorth = -1;
     for (const p of this.state.players) {
       if (!p.alive) continue;
-      let worth = p.cash;
-      for (const prop of this.state.properties) {
-        if (prop.owner === p.index) {
-          const tile = PROPERTY_TILES[prop.index];
-          worth += prop.mortgaged ? Math.floor(tile.mortgageValue / 2) : tile.price;
-          // Add house value (sell at half cost)
-          if (prop.houses > 0 && tile.houseCost) {
-            worth += prop.houses * Math.floor(tile.houseCost / 2);
-          }
-        }
-      }
+      const worth = this.netWorth(p.index);
       if (worth > bestWorth) {
         bestWorth = worth;
         bestPlayer = p.index;
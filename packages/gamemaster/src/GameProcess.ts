@@ -42,10 +42,11 @@ export class GameProcess {
     playersPacked: bigint,
     propertiesPacked: bigint,
     metaPacked: bigint,
+    houses?: number[],
   ): GameProcess {
     const process = new GameProcess(config);
     process.engine = MonopolyEngine.fromCheckpoint(
-      config.players, config.diceSeed, playersPacked, propertiesPacked, metaPacked,
+      config.players, config.diceSeed, playersPacked, propertiesPacked, metaPacked, houses,
     );
     return process;
   }
@@ -210,9 +211,9 @@ export class GameProcess {
     }
     this.checkpointInProgress = true;
     try {
-      const { playersPacked, propertiesPacked, metaPacked } = this.engine.packForCheckpoint();
+      const { playersPacked, propertiesPacked, metaPacked, houses } = this.engine.packForCheckpoint();
       const txHash = await this.config.settlement.writeCheckpoint(
-        this.config.gameId, round, playersPacked, propertiesPacked, metaPacked,
+        this.config.gameId, round, playersPacked, propertiesPacked, metaPacked, houses,
       );
       console.log(`[Game ${this.config.gameId}] Checkpoint round ${round}: ${txHash}`);
       await new Promise((r) => setTimeout(r, 300)); // let nonce propagate on RPC
@@ -259,7 +260,9 @@ export class GameProcess {
           await new Promise((r) => setTimeout(r, wait));
         }
         try {
-          txHash = await this.config.settlement!.settleGame(this.config.gameId, winnerAddr, logHash);
+          txHash = await this.config.settlement!.settleGame(
+            this.config.gameId, winnerAddr, logHash, this.finishingOrder(),
+          );
           console.log(`[Game ${this.config.gameId}] Settled on-chain: ${txHash}`);
           break;
         } catch (err) {
@@ -293,6 +296,22 @@ export class GameProcess {
 
   // ========== HELPERS ==========
 
+  /**
+   * Player addresses in finishing order: the winner, then anyone else still standing by net
+   * worth (games that hit the round limit), then the bankrupt, last to go bankrupt first.
+   */
+  private finishingOrder(): string[] {
+    const winner = this.engine.state.winner;
+    const bankrupt = this.events
+      .flatMap((e) => (e.type === "playerBankrupt" ? [e.player] : []))
+      .reverse();
+    const standing = this.engine.state.players
+      .map((p) => p.index)
+      .filter((i) => i !== winner && !bankrupt.includes(i))
+      .sort((a, b) => this.engine.netWorth(b) - this.engine.netWorth(a));
+    return [winner, ...standing, ...bankrupt].map((i) => this.config.players[i]);
+  }
+
   private sendToAgent(address: string, msg: any): void {
     const socket = this.agentSockets.get(address);
     if (socket && socket.readyState === WebSocket.OPEN) {
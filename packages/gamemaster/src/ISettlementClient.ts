@@ -7,6 +7,18 @@ export interface GameInfo {
   winner: string;
   revealDeadline: bigint;
   winnerPaid: boolean;
+  /** Solana only: unix time after which a pending settlement can no longer be disputed. */
+  challengeDeadline?: bigint;
+  /** Solana only: per seat, whether its player has paid in (a game can start short of full). */
+  deposited?: boolean[];
+  /** Solana only: number of paid places in the game's payout table. */
+  payoutPlaces?: number;
+  /** Solana only: deposits needed before the GM can start the game short of full. */
+  minPlayers?: number;
+  /** Solana only: unix time after which an unstarted game can be cancelled. */
+  depositDeadline?: bigint;
+  /** Solana only: lobby page listing the game while it is open. */
+  lobbyPage?: number;
 }
 
 export interface CheckpointInfo {
@@ -14,6 +26,8 @@ export interface CheckpointInfo {
   playersPacked: bigint;
   propertiesPacked: bigint;
   metaPacked: bigint;
+  /** Houses per property; absent when the chain does not store them (EVM, v1 Solana checkpoints). */
+  houses?: number[];
 }
 
 export interface ISettlementClient {
@@ -23,9 +37,16 @@ export interface ISettlementClient {
     playersPacked: bigint,
     propertiesPacked: bigint,
     metaPacked: bigint,
+    houses: number[],
   ): Promise<string>;
 
-  settleGame(gameId: number, winnerAddress: string, gameLogHash: string): Promise<string>;
+  /** `ranking` is the full finishing order, winner first; chains that only record a winner ignore it. */
+  settleGame(
+    gameId: number,
+    winnerAddress: string,
+    gameLogHash: string,
+    ranking?: string[],
+  ): Promise<string>;
 
   getGame(gameId: number): Promise<GameInfo>;
 
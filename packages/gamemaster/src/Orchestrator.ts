@@ -130,6 +130,7 @@ export class Orchestrator {
         checkpoint.playersPacked,
         checkpoint.propertiesPacked,
         checkpoint.metaPacked,
+        checkpoint.houses,
       );
     } else {
       process = new GameProcess(config);
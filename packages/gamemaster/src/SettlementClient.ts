@@ -18,13 +18,14 @@ export class SettlementClient implements ISettlementClient {
     this.contract = new Contract(contractAddress, SETTLEMENT_ABI, this.wallet);
   }
 
-  /** Write a checkpoint to the chain. */
+  /** Write a checkpoint to the chain. The contract has no house storage, so houses are dropped. */
   async writeCheckpoint(
     gameId: number,
     round: number,
     playersPacked: bigint,
     propertiesPacked: bigint,
     metaPacked: bigint,
+    _houses: number[],
   ): Promise<string> {
     const tx = await this.contract.checkpoint(gameId, round, playersPacked, propertiesPacked, metaPacked);
     const receipt = await tx.wait();
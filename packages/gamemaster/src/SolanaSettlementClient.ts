@@ -12,6 +12,26 @@ import * as fs from "fs";
 import { ISettlementClient, GameInfo, CheckpointInfo } from "./ISettlementClient";
 
 const ZERO_KEY = new PublicKey("11111111111111111111111111111111");
+const DEFAULT_ENTRY_FEE = 10_000_000n; // 0.01 SOL
+const DEFAULT_MAX_PLAYERS = 4;
+const DEFAULT_PAYOUT_BPS = [10_000]; // winner takes the whole prize pool
+const LOBBY_PAGE_SIZE = 32;
+// A table that reached min_players starts short this close to its deposit deadline rather than
+// waiting to fill and being cancelled
+const START_SHORT_MARGIN_S = 60n;
+const MAX_PLAYERS = 8;
+const NUM_PROPERTIES = 28;
+const CHECKPOINT_HISTORY = 8;
+// CheckpointEntry: round + version + player slots + properties + meta + houses
+const CHECKPOINT_ENTRY_SIZE = 8 + 1 + MAX_PLAYERS * 8 + 16 + 16 + NUM_PROPERTIES;
+// 8 disc + game_id + count + history + bump + payer
+const CHECKPOINT_SIZE = 8 + 8 + 8 + CHECKPOINT_HISTORY * CHECKPOINT_ENTRY_SIZE + 1 + 32;
+// Single-checkpoint layouts that migrate_checkpoint upgrades: v1 (players in one u128, no
+// houses, no payer) and v2 (game_id followed by one CheckpointEntry)
+const CHECKPOINT_V1_SIZE = 8 + 8 + 8 + 16 * 3 + 1;
+// PlatformConfig offsets: 8 disc + 32 owner + 32 gm + 32 platformFee = 104
+const GAME_COUNT_OFFSET = 104;
+const LOBBY_PAGE_COUNT_OFFSET = GAME_COUNT_OFFSET + 8 + 4; // after game_count, open_game_count
 
 function anchorDisc(namespace: string, name: string): Buffer {
   return crypto
@@ -36,15 +56,42 @@ function writeBigU128LE(buf: Buffer, value: bigint, offset: number): void {
   buf.writeBigUInt64LE(value >> 64n, offset + 8);
 }
 
+/** A keypair given as a JSON byte array or a path to a keypair file. */
+function loadKeypair(source: string, name: string): Keypair {
+  if (source.startsWith("[")) {
+    return Keypair.fromSecretKey(new Uint8Array(JSON.parse(source) as number[]));
+  }
+  if (fs.existsSync(source)) {
+    return Keypair.fromSecretKey(new Uint8Array(JSON.parse(fs.readFileSync(source, "utf-8")) as number[]));
+  }
+  throw new Error(`${name} must be a JSON byte array or a path to a keypair file`);
+}
+
 function readBigU128LE(data: Buffer, offset: number): bigint {
   const lo = data.readBigUInt64LE(offset);
   const hi = data.readBigUInt64LE(offset + 8);
   return (hi << 64n) | lo;
 }
 
+function readCheckpointEntry(d: Buffer, off: number): CheckpointInfo {
+  const round = Number(d.readBigUInt64LE(off)); off += 8;
+  const version = d.readUInt8(off); off += 1;
+  let playersPacked = 0n;
+  for (let i = 0; i < MAX_PLAYERS; i++, off += 8) {
+    playersPacked |= d.readBigUInt64LE(off) << (BigInt(i) * 64n);
+  }
+  const propertiesPacked = readBigU128LE(d, off); off += 16;
+  const metaPacked = readBigU128LE(d, off); off += 16;
+  // Entries carried over from v1 have no houses
+  const houses = version >= 2 ? Array.from(d.subarray(off, off + NUM_PROPERTIES)) : undefined;
+  return { round, playersPacked, propertiesPacked, metaPacked, houses };
+}
+
 export class SolanaSettlementClient implements ISettlementClient {
   private connection: Connection;
   private keypair: Keypair;
+  /** GM committee members that co-sign settle_game alongside the GM */
+  private cosigners: Keypair[];
   private programId: PublicKey;
   private platformPda: PublicKey;
   private platformBump: number;
@@ -52,19 +99,17 @@ export class SolanaSettlementClient implements ISettlementClient {
   private pollTimer: ReturnType<typeof setInterval> | null = null;
   private gameStartedCallback: ((gameId: number, diceSeed: string) => void) | null = null;
 
-  constructor(rpcUrl: string, programIdStr: string, gmKeypairSource: string) {
+  constructor(
+    rpcUrl: string,
+    programIdStr: string,
+    gmKeypairSource: string,
+    cosignerKeypairSources: string[] = [],
+  ) {
     this.connection = new Connection(rpcUrl, "confirmed");
     this.programId = new PublicKey(programIdStr);
 
-    if (gmKeypairSource.startsWith("[")) {
-      const bytes = JSON.parse(gmKeypairSource) as number[];
-      this.keypair = Keypair.fromSecretKey(new Uint8Array(bytes));
-    } else if (fs.existsSync(gmKeypairSource)) {
-      const bytes = JSON.parse(fs.readFileSync(gmKeypairSource, "utf-8")) as number[];
-      this.keypair = Keypair.fromSecretKey(new Uint8Array(bytes));
-    } else {
-      throw new Error("GM_SOLANA_KEYPAIR must be a JSON byte array or a path to a keypair file");
-    }
+    this.keypair = loadKeypair(gmKeypairSource, "GM_SOLANA_KEYPAIR");
+    this.cosigners = cosignerKeypairSources.map(s => loadKeypair(s, "GM_SOLANA_COSIGNERS"));
 
     [this.platformPda, this.platformBump] = PublicKey.findProgramAddressSync(
       [Buffer.from("platform")],
@@ -83,6 +128,26 @@ export class SolanaSettlementClient implements ISettlementClient {
     );
   }
 
+  private lobbyPda(page: number): [PublicKey, number] {
+    const b = Buffer.alloc(4);
+    b.writeUInt32LE(page);
+    return PublicKey.findProgramAddressSync([Buffer.from("lobby"), b], this.programId);
+  }
+
+  private vaultPda(gameId: number): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), gameIdBuf(gameId)],
+      this.programId,
+    );
+  }
+
+  private treasuryVaultPda(mint: string): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), new PublicKey(mint).toBuffer()],
+      this.programId,
+    );
+  }
+
   private checkpointPda(gameId: number): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
       [Buffer.from("checkpoint"), gameIdBuf(gameId)],
@@ -90,6 +155,20 @@ export class SolanaSettlementClient implements ISettlementClient {
     );
   }
 
+  /** Grows a single-checkpoint account into the history layout; the GM pays the extra rent. */
+  private migrateCheckpointIx(gameId: number): TransactionInstruction {
+    return new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.gamePda(gameId)[0], isSigner: false, isWritable: false },
+        { pubkey: this.checkpointPda(gameId)[0], isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data: anchorDisc("global", "migrate_checkpoint"),
+    });
+  }
+
   private async sendIx(ix: TransactionInstruction): Promise<string> {
     const tx = new Transaction().add(ix);
     return sendAndConfirmTransaction(this.connection, tx, [this.keypair]);
@@ -97,11 +176,98 @@ export class SolanaSettlementClient implements ISettlementClient {
 
   // ========== CREATE OPEN GAME ==========
 
-  async createOpenGame(): Promise<void> {
+  /**
+   * Create an open game. Pass `token` for a token-denominated table: `entryFee` is then in
+   * the mint's base units and the program opens a vault token account for the pot.
+   */
+  async createOpenGame(
+    entryFee: bigint = DEFAULT_ENTRY_FEE,
+    maxPlayers: number = DEFAULT_MAX_PLAYERS,
+    minPlayers?: number,
+    payoutBps: number[] = DEFAULT_PAYOUT_BPS,
+    token?: { mint: string; tokenProgram: string },
+  ): Promise<void> {
     const gameCount = await this.getGameCount();
     const [gamePda] = this.gamePda(gameCount);
+    const page = await this.findLobbyPageWithSpace();
 
     const disc = anchorDisc("global", "create_open_game");
+    // min_players and timeouts are Option<_>; timeouts fall back to the platform defaults
+    const data = Buffer.alloc(
+      8 + 8 + 1 + (minPlayers === undefined ? 1 : 2) + 1 + 4 + payoutBps.length * 2 + 4,
+    );
+    disc.copy(data, 0);
+    data.writeBigUInt64LE(entryFee, 8);
+    data.writeUInt8(maxPlayers, 16);
+    let off = 17;
+    if (minPlayers === undefined) {
+      data.writeUInt8(0, off); off += 1;
+    } else {
+      data.writeUInt8(1, off); off += 1;
+      data.writeUInt8(minPlayers, off); off += 1;
+    }
+    data.writeUInt8(0, off); off += 1;
+    data.writeUInt32LE(payoutBps.length, off); off += 4;
+    for (const bps of payoutBps) { data.writeUInt16LE(bps, off); off += 2; }
+    data.writeUInt32LE(page, off);
+
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.platformPda, isSigner: false, isWritable: true },
+        { pubkey: gamePda, isSigner: false, isWritable: true },
+        { pubkey: this.lobbyPda(page)[0], isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      ],
+      data,
+    });
+    if (token) {
+      ix.keys.push(
+        { pubkey: new PublicKey(token.mint), isSigner: false, isWritable: false },
+        { pubkey: this.vaultPda(gameCount)[0], isSigner: false, isWritable: true },
+        { pubkey: new PublicKey(token.tokenProgram), isSigner: false, isWritable: false },
+        { pubkey: this.treasuryVaultPda(token.mint)[0], isSigner: false, isWritable: true },
+      );
+    }
+
+    await this.sendIx(ix);
+  }
+
+  // ========== CREATE GAME (FIXED ROSTER) ==========
+
+  /**
+   * Create a game for players already paired off-chain. The game opens in Depositing and only
+   * the listed players can deposit. Returns the new game id.
+   */
+  async createGame(
+    players: string[],
+    entryFee: bigint = DEFAULT_ENTRY_FEE,
+    minPlayers?: number,
+    payoutBps: number[] = DEFAULT_PAYOUT_BPS,
+    token?: { mint: string; tokenProgram: string },
+  ): Promise<number> {
+    const gameCount = await this.getGameCount();
+    const [gamePda] = this.gamePda(gameCount);
+
+    const disc = anchorDisc("global", "create_game");
+    const data = Buffer.alloc(
+      8 + 4 + players.length * 32 + 8 + (minPlayers === undefined ? 1 : 2) + 1 + 4 + payoutBps.length * 2,
+    );
+    disc.copy(data, 0);
+    let off = 8;
+    data.writeUInt32LE(players.length, off); off += 4;
+    for (const addr of players) { new PublicKey(addr).toBuffer().copy(data, off); off += 32; }
+    data.writeBigUInt64LE(entryFee, off); off += 8;
+    if (minPlayers === undefined) {
+      data.writeUInt8(0, off); off += 1;
+    } else {
+      data.writeUInt8(1, off); off += 1;
+      data.writeUInt8(minPlayers, off); off += 1;
+    }
+    data.writeUInt8(0, off); off += 1; // timeouts: platform defaults
+    data.writeUInt32LE(payoutBps.length, off); off += 4;
+    for (const bps of payoutBps) { data.writeUInt16LE(bps, off); off += 2; }
 
     const ix = new TransactionInstruction({
       programId: this.programId,
@@ -111,10 +277,44 @@ export class SolanaSettlementClient implements ISettlementClient {
         { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
-      data: disc,
+      data,
     });
+    if (token) {
+      ix.keys.push(
+        { pubkey: new PublicKey(token.mint), isSigner: false, isWritable: false },
+        { pubkey: this.vaultPda(gameCount)[0], isSigner: false, isWritable: true },
+        { pubkey: new PublicKey(token.tokenProgram), isSigner: false, isWritable: false },
+        { pubkey: this.treasuryVaultPda(token.mint)[0], isSigner: false, isWritable: true },
+      );
+    }
 
     await this.sendIx(ix);
+    return gameCount;
+  }
+
+  // ========== START REVEAL ==========
+
+  /**
+   * Start the reveal phase of a game that has reached its `min_players` without filling. Open
+   * games pass their lobby page so the game leaves the lobby; fixed-roster games pass none.
+   */
+  async startReveal(gameId: number, lobbyPage?: number): Promise<string> {
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.platformPda, isSigner: false, isWritable: true },
+        { pubkey: this.gamePda(gameId)[0], isSigner: false, isWritable: true },
+        // Anchor's `None` is the program id
+        {
+          pubkey: lobbyPage === undefined ? this.programId : this.lobbyPda(lobbyPage)[0],
+          isSigner: false,
+          isWritable: lobbyPage !== undefined,
+        },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+      ],
+      data: anchorDisc("global", "start_reveal"),
+    });
+    return this.sendIx(ix);
   }
 
   // ========== WRITE CHECKPOINT ==========
@@ -125,17 +325,23 @@ export class SolanaSettlementClient implements ISettlementClient {
     playersPacked: bigint,
     propertiesPacked: bigint,
     metaPacked: bigint,
+    houses: number[],
   ): Promise<string> {
     const [gamePda] = this.gamePda(gameId);
     const [cpPda] = this.checkpointPda(gameId);
 
     const disc = anchorDisc("global", "write_checkpoint");
-    const data = Buffer.alloc(8 + 8 + 16 + 16 + 16);
+    const data = Buffer.alloc(8 + 8 + MAX_PLAYERS * 8 + 16 + 16 + NUM_PROPERTIES);
     disc.copy(data, 0);
     data.writeBigUInt64LE(BigInt(round), 8);
-    writeBigU128LE(data, playersPacked, 16);
-    writeBigU128LE(data, propertiesPacked, 32);
-    writeBigU128LE(data, metaPacked, 48);
+    let off = 16;
+    // The engine packs one 64-bit slot per player; the program stores them as [u64; MAX_PLAYERS]
+    for (let i = 0; i < MAX_PLAYERS; i++, off += 8) {
+      data.writeBigUInt64LE((playersPacked >> (BigInt(i) * 64n)) & 0xffff_ffff_ffff_ffffn, off);
+    }
+    writeBigU128LE(data, propertiesPacked, off); off += 16;
+    writeBigU128LE(data, metaPacked, off); off += 16;
+    houses.forEach((h, i) => data.writeUInt8(h, off + i));
 
     const ix = new TransactionInstruction({
       programId: this.programId,
@@ -149,22 +355,52 @@ export class SolanaSettlementClient implements ISettlementClient {
       data,
     });
 
-    return this.sendIx(ix);
+    const tx = new Transaction();
+    const cpInfo = await this.connection.getAccountInfo(cpPda);
+    if (cpInfo && cpInfo.data.length !== CHECKPOINT_SIZE) {
+      tx.add(this.migrateCheckpointIx(gameId));
+    }
+    tx.add(ix);
+
+    return sendAndConfirmTransaction(this.connection, tx, [this.keypair]);
   }
 
   // ========== SETTLE GAME ==========
 
-  async settleGame(gameId: number, winnerAddress: string, gameLogHash: string): Promise<string> {
+  /**
+   * Settle with a full finishing order. When `ranking` is omitted the winner is
+   * placed first and the remaining depositors follow in seat order, which is only
+   * correct for winner-takes-all payout tables, so other tables require `ranking`.
+   */
+  async settleGame(
+    gameId: number,
+    winnerAddress: string,
+    gameLogHash: string,
+    ranking?: string[],
+  ): Promise<string> {
     const [gamePda] = this.gamePda(gameId);
 
+    if (!ranking) {
+      // Only depositors can be ranked; a fixed-roster game started at min_players has empty seats
+      const game = await this.getGame(gameId);
+      if ((game.payoutPlaces ?? 1) > 1) {
+        throw new Error(`Game ${gameId} pays ${game.payoutPlaces} places; settle it with a ranking`);
+      }
+      ranking = [
+        winnerAddress,
+        ...game.players.filter((p, i) => p && p !== winnerAddress && game.deposited?.[i]),
+      ];
+    }
+
     const disc = anchorDisc("global", "settle_game");
-    const winnerKey = new PublicKey(winnerAddress);
     const hashBytes = Buffer.from(gameLogHash.replace(/^0x/, ""), "hex");
 
-    const data = Buffer.alloc(8 + 32 + 32);
+    const data = Buffer.alloc(8 + 4 + ranking.length * 32 + 32);
     disc.copy(data, 0);
-    winnerKey.toBuffer().copy(data, 8);
-    hashBytes.copy(data, 40, 0, 32);
+    let off = 8;
+    data.writeUInt32LE(ranking.length, off); off += 4;
+    for (const addr of ranking) { new PublicKey(addr).toBuffer().copy(data, off); off += 32; }
+    hashBytes.copy(data, off, 0, 32);
 
     const ix = new TransactionInstruction({
       programId: this.programId,
@@ -172,11 +408,68 @@ export class SolanaSettlementClient implements ISettlementClient {
         { pubkey: this.platformPda, isSigner: false, isWritable: false },
         { pubkey: gamePda, isSigner: false, isWritable: true },
         { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+        ...this.cosigners.map(kp => ({ pubkey: kp.publicKey, isSigner: true, isWritable: false })),
       ],
       data,
     });
 
-    return this.sendIx(ix);
+    const tx = new Transaction().add(ix);
+    return sendAndConfirmTransaction(this.connection, tx, [this.keypair, ...this.cosigners]);
+  }
+
+  // ========== CLOSE GAME ==========
+
+  /**
+   * Reclaim rent from a finished game once every payout or refund has gone out: closes the
+   * checkpoint (if one was written) and then the game. Token games also close their vault.
+   */
+  async closeGame(gameId: number, token?: { mint: string; tokenProgram: string }): Promise<string> {
+    const [gamePda] = this.gamePda(gameId);
+    const [cpPda] = this.checkpointPda(gameId);
+
+    const tx = new Transaction();
+    const cpInfo = await this.connection.getAccountInfo(cpPda);
+    if (cpInfo) {
+      if (cpInfo.data.length !== CHECKPOINT_SIZE) {
+        tx.add(this.migrateCheckpointIx(gameId));
+      }
+      // payer is the last field of GameCheckpoint from v2 on; migrating a v1 account credits the
+      // game's rent payer, which is this GM
+      const payer = cpInfo.data.length === CHECKPOINT_V1_SIZE
+        ? this.keypair.publicKey
+        : new PublicKey(cpInfo.data.subarray(cpInfo.data.length - 32));
+      tx.add(new TransactionInstruction({
+        programId: this.programId,
+        keys: [
+          { pubkey: gamePda, isSigner: false, isWritable: false },
+          { pubkey: cpPda, isSigner: false, isWritable: true },
+          { pubkey: payer, isSigner: false, isWritable: true },
+          { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+        ],
+        data: anchorDisc("global", "close_checkpoint"),
+      }));
+    }
+
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: gamePda, isSigner: false, isWritable: true },
+        { pubkey: cpPda, isSigner: false, isWritable: false },
+        { pubkey: this.keypair.publicKey, isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+      ],
+      data: anchorDisc("global", "close_game"),
+    });
+    if (token) {
+      ix.keys.push(
+        { pubkey: this.vaultPda(gameId)[0], isSigner: false, isWritable: true },
+        { pubkey: new PublicKey(token.mint), isSigner: false, isWritable: false },
+        { pubkey: new PublicKey(token.tokenProgram), isSigner: false, isWritable: false },
+      );
+    }
+    tx.add(ix);
+
+    return sendAndConfirmTransaction(this.connection, tx, [this.keypair]);
   }
 
   // ========== READ STATE ==========
@@ -192,11 +485,16 @@ export class SolanaSettlementClient implements ISettlementClient {
     const gid = Number(d.readBigUInt64LE(off)); off += 8;
     const status = d[off]; off += 1;
 
+    const numSeats = d.readUInt32LE(off); off += 4;
     const players: string[] = [];
-    for (let i = 0; i < 4; i++) { players.push(readPubkey(d, off)); off += 32; }
+    for (let i = 0; i < numSeats; i++) { players.push(readPubkey(d, off)); off += 32; }
 
-    off += 4 * 32; // skip commit_hashes
-    off += 4 * 32; // skip revealed_secrets
+    off += 4; // commit_hashes: a seat has deposited once its commit is set
+    const deposited: boolean[] = [];
+    for (let i = 0; i < numSeats; i++) {
+      deposited.push(d.subarray(off, off + 32).some(b => b !== 0)); off += 32;
+    }
+    off += 4 + numSeats * 32; // skip revealed_secrets
 
     const depositCount = d[off]; off += 1;
     const revealCount = d[off]; off += 1;
@@ -212,7 +510,23 @@ export class SolanaSettlementClient implements ISettlementClient {
     off += 8; // created_at
     off += 8; // started_at
 
-    const winnerPaid = d[off] !== 0;
+    const numPaid = d.readUInt32LE(off); off += 4;
+    const winnerSeat = players.indexOf(winner);
+    const winnerPaid = winnerSeat >= 0 && winnerSeat < numPaid && d[off + winnerSeat] !== 0;
+    off += numPaid;
+
+    off += 1 + 8 + 1; // bump, entry_fee, max_players
+    const minPlayers = d[off]; off += 1;
+    off += 32; // timeouts
+    const depositDeadline = d.readBigInt64LE(off); off += 8;
+    off += 8 + 2; // game_deadline, platform_fee_bps
+    const payoutPlaces = d.readUInt32LE(off);
+    off += 4 + payoutPlaces * 2; // payout_bps
+    off += 4 + d.readUInt32LE(off) * 32; // ranking
+    off += 1 + 32 + 32; // platform_paid, mint, claw_mint
+    const lobbyPage = d.readUInt32LE(off); off += 4;
+    off += 32 + 1; // rent_payer, claw_accounts_open
+    const challengeDeadline = d.readBigInt64LE(off);
 
     return {
       players: players.map(p => p === ZERO_KEY.toBase58() ? "" : p),
@@ -223,49 +537,101 @@ export class SolanaSettlementClient implements ISettlementClient {
       winner: winner === ZERO_KEY.toBase58() ? "" : winner,
       revealDeadline,
       winnerPaid,
+      challengeDeadline,
+      deposited,
+      payoutPlaces,
+      minPlayers,
+      depositDeadline,
+      lobbyPage,
     };
   }
 
   async getCheckpoint(gameId: number): Promise<CheckpointInfo> {
+    const [latest] = await this.getCheckpointHistory(gameId);
+    return latest ?? { round: 0, playersPacked: 0n, propertiesPacked: 0n, metaPacked: 0n };
+  }
+
+  /** Retained checkpoints, newest first. Accounts still in a single-checkpoint layout yield one. */
+  async getCheckpointHistory(gameId: number): Promise<CheckpointInfo[]> {
     const [cpPda] = this.checkpointPda(gameId);
     const info = await this.connection.getAccountInfo(cpPda);
-    if (!info) return { round: 0, playersPacked: 0n, propertiesPacked: 0n, metaPacked: 0n };
+    if (!info) return [];
 
     const d = info.data;
-    let off = 8; // discriminator
-    off += 8; // game_id
-    const round = Number(d.readBigUInt64LE(off)); off += 8;
-    const playersPacked = readBigU128LE(d, off); off += 16;
-    const propertiesPacked = readBigU128LE(d, off); off += 16;
-    const metaPacked = readBigU128LE(d, off);
-
-    return { round, playersPacked, propertiesPacked, metaPacked };
+    const body = 8 + 8; // discriminator, game_id
+    if (d.length === CHECKPOINT_V1_SIZE) {
+      // v1 only had room for the first two player slots and no houses
+      let off = body;
+      const round = Number(d.readBigUInt64LE(off)); off += 8;
+      const playersPacked = readBigU128LE(d, off); off += 16;
+      const propertiesPacked = readBigU128LE(d, off); off += 16;
+      const metaPacked = readBigU128LE(d, off);
+      return [{ round, playersPacked, propertiesPacked, metaPacked }];
+    }
+    if (d.length !== CHECKPOINT_SIZE) {
+      return [readCheckpointEntry(d, body)]; // v2
+    }
+
+    const count = Number(d.readBigUInt64LE(body));
+    const history = body + 8;
+    const retained = Math.min(count, CHECKPOINT_HISTORY);
+    return Array.from({ length: retained }, (_, back) => {
+      const slot = (count - 1 - back) % CHECKPOINT_HISTORY;
+      return readCheckpointEntry(d, history + slot * CHECKPOINT_ENTRY_SIZE);
+    });
   }
 
   async getGameCount(): Promise<number> {
     const info = await this.connection.getAccountInfo(this.platformPda);
     if (!info) return 0;
-    // game_count offset: 8 disc + 32 owner + 32 gm + 32 platformFee = 104
-    return Number(info.data.readBigUInt64LE(104));
+    return Number(info.data.readBigUInt64LE(GAME_COUNT_OFFSET));
   }
 
-  async getOpenGameIds(): Promise<number[]> {
+  /** Open game ids per lobby page, for pages `0..lobby_page_count`. */
+  private async getLobbyPages(): Promise<number[][]> {
     const info = await this.connection.getAccountInfo(this.platformPda);
     if (!info) return [];
+    const pageCount = info.data.readUInt32LE(LOBBY_PAGE_COUNT_OFFSET);
+
+    const pdas = Array.from({ length: pageCount }, (_, i) => this.lobbyPda(i)[0]);
+    const pages = await this.connection.getMultipleAccountsInfo(pdas);
+    return pages.map(page => {
+      if (!page) return [];
+      const d = page.data;
+      const off = 8 + 4; // discriminator, page
+      const len = d.readUInt32LE(off);
+      return Array.from({ length: len }, (_, i) => Number(d.readBigUInt64LE(off + 4 + i * 8)));
+    });
+  }
 
-    const d = info.data;
-    // open_game_count offset: 8 + 32 + 32 + 32 + 8 + (20*8) = 272
-    const count = d[272];
-    // open_game_ids offset: 8 + 32 + 32 + 32 + 8 = 112
-    const ids: number[] = [];
-    for (let i = 0; i < count; i++) {
-      ids.push(Number(d.readBigUInt64LE(112 + i * 8)));
-    }
-    return ids;
+  /** First lobby page with a free slot, or the next new page. */
+  private async findLobbyPageWithSpace(): Promise<number> {
+    const pages = await this.getLobbyPages();
+    const page = pages.findIndex(ids => ids.length < LOBBY_PAGE_SIZE);
+    return page === -1 ? pages.length : page;
+  }
+
+  async getOpenGameIds(): Promise<number[]> {
+    return (await this.getLobbyPages()).flat();
   }
 
   // ========== EVENT POLLING ==========
 
+  /** Starts a game that has its `min_players` deposits once its deposit window is about to close. */
+  private async startIfShortAndDue(gameId: number, g: GameInfo): Promise<void> {
+    const now = BigInt(Math.floor(Date.now() / 1000));
+    if (
+      g.minPlayers === undefined
+      || g.depositCount < g.minPlayers
+      || g.depositDeadline === undefined
+      || g.depositDeadline - now > START_SHORT_MARGIN_S
+    ) {
+      return;
+    }
+    const txHash = await this.startReveal(gameId, g.status === 1 ? g.lobbyPage : undefined);
+    console.log(`[SolanaSettlement] Game ${gameId} started with ${g.depositCount} players: ${txHash}`);
+  }
+
   onGameStarted(callback: (gameId: number, diceSeed: string) => void): void {
     this.gameStartedCallback = callback;
 
@@ -281,6 +647,8 @@ export class SolanaSettlementClient implements ISettlementClient {
             if (g.status === 4) { // STARTED
               this.reportedGames.add(id);
               this.gameStartedCallback?.(id, g.diceSeed);
+            } else if (g.status === 1 || g.status === 2) { // OPEN, DEPOSITING
+              await this.startIfShortAndDue(id, g);
             }
           } catch { /* game may not exist yet */ }
         }
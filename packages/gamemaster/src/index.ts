@@ -19,6 +19,8 @@ const OPEN_GAME_REPLENISH_INTERVAL_MS = parseInt(process.env.OPEN_GAME_REPLENISH
 const SOLANA_RPC_URL = process.env.SOLANA_RPC_URL || "https://api.devnet.solana.com";
 const SOLANA_PROGRAM_ID = process.env.SOLANA_PROGRAM_ID || "";
 const GM_SOLANA_KEYPAIR = process.env.GM_SOLANA_KEYPAIR || "";
+// GM committee co-signers for settle_game, ';'-separated keypair JSON arrays or file paths
+const GM_SOLANA_COSIGNERS = (process.env.GM_SOLANA_COSIGNERS || "").split(";").filter(Boolean);
 
 // BNB
 const BNB_RPC_URL = process.env.RPC_URL || process.env.BNB_RPC_URL || "https://data-seed-prebsc-1-s1.binance.org:8545";
@@ -35,7 +37,7 @@ const settlements = new Map<Chain, ISettlementClient>();
 if (!LOCAL_MODE) {
   if (SOLANA_PROGRAM_ID && GM_SOLANA_KEYPAIR) {
     const { SolanaSettlementClient } = require("./SolanaSettlementClient");
-    settlements.set("solana", new SolanaSettlementClient(SOLANA_RPC_URL, SOLANA_PROGRAM_ID, GM_SOLANA_KEYPAIR));
+    settlements.set("solana", new SolanaSettlementClient(SOLANA_RPC_URL, SOLANA_PROGRAM_ID, GM_SOLANA_KEYPAIR, GM_SOLANA_COSIGNERS));
     console.log(`[GM Server] Solana: program=${SOLANA_PROGRAM_ID}`);
   }
   if (BNB_SETTLEMENT_ADDRESS && BNB_GM_PRIVATE_KEY) {
@@ -153,6 +155,7 @@ app.get("/games/slots", async (req, res) => {
 });
 
 const STATUS_SETTLED = 5;
+const STATUS_PENDING_SETTLEMENT = 7;
 const HISTORY_LIMIT = 100;
 
 app.get("/games/history", async (req, res) => {
@@ -212,11 +215,14 @@ app.get("/games/:gameId", async (req, res) => {
     }
     const settlement = settlements.get(chain)!;
     const g = await settlement.getGame(gameId);
-    const settled = g.status === STATUS_SETTLED;
+    // A pending settlement pays out once its challenge period passes; the first withdraw finalizes it
+    const settled = g.status === STATUS_SETTLED
+      || (g.status === STATUS_PENDING_SETTLEMENT && g.challengeDeadline !== undefined
+        && g.challengeDeadline < BigInt(Math.floor(Date.now() / 1000)));
     res.json({
       gameId,
       status: g.status,
-      statusLabel: ["PENDING", "OPEN", "DEPOSITING", "REVEALING", "STARTED", "SETTLED", "VOIDED"][g.status] ?? "UNKNOWN",
+      statusLabel: ["PENDING", "OPEN", "DEPOSITING", "REVEALING", "STARTED", "SETTLED", "VOIDED", "PENDING_SETTLEMENT", "DISPUTED"][g.status] ?? "UNKNOWN",
       settlementConcluded: settled,
       winnerCanWithdraw: settled && !!g.winner && !g.winnerPaid,
       winnerClaimed: !!g.winnerPaid,
@@ -2,14 +2,14 @@ import {
   Connection,
   Keypair,
   PublicKey,
+  SimulatedTransactionResponse,
   SystemProgram,
   Transaction,
   TransactionInstruction,
   sendAndConfirmTransaction,
 } from "@solana/web3.js";
 import * as crypto from "crypto";
-
-const ENTRY_FEE = 10_000_000; // 0.01 SOL
+import { getBytes, keccak256 } from "ethers";
 
 function anchorDisc(namespace: string, name: string): Buffer {
   return crypto
@@ -25,21 +25,48 @@ function gameIdBuf(gameId: number): Buffer {
   return b;
 }
 
+/**
+ * Commit hash for a secret, bound to the game and the player's seat: `keccak256(game_id (u64 LE)
+ * ‖ player ‖ secret)`. Matches the program's `COMMIT_SCHEME` 1; games created before it
+ * (`commit_scheme` 0) commit to `keccak256(secret)` alone.
+ */
+export function commitmentHash(
+  gameId: number,
+  player: PublicKey,
+  secret: Uint8Array,
+  scheme = 1,
+): Uint8Array {
+  const preimage =
+    scheme === 0
+      ? Buffer.from(secret)
+      : Buffer.concat([gameIdBuf(gameId), player.toBuffer(), Buffer.from(secret)]);
+  return getBytes(keccak256(preimage));
+}
+
 function readPubkey(data: Buffer, offset: number): string {
   return new PublicKey(data.subarray(offset, offset + 32)).toBase58();
 }
 
 const ZERO_KEY = new PublicKey("11111111111111111111111111111111");
 
+/** Token accounts for games created with a mint. SOL games leave these out. */
+export interface TokenGameAccounts {
+  mint: PublicKey;
+  tokenProgram: PublicKey;
+  /** The agent's token account for the game mint */
+  tokenAccount: PublicKey;
+}
+
 /**
  * Agent-side Solana client for the MonopolySettlement program.
- * Handles: depositAndCommit, revealSeed, withdraw.
+ * Handles: depositAndCommit, leaveGame, revealSeed, withdraw, claimRefund, disputeSettlement, and auditing the GM's dice rolls.
  */
 export class SolanaSettlementClient {
   private connection: Connection;
   private keypair: Keypair;
   private programId: PublicKey;
   private platformPda: PublicKey;
+  private treasuryPda: PublicKey;
   private secret: Uint8Array | null = null;
 
   constructor(rpcUrl: string, programIdStr: string, agentKeypairJson: string) {
@@ -53,6 +80,10 @@ export class SolanaSettlementClient {
       [Buffer.from("platform")],
       this.programId,
     );
+    [this.treasuryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      this.programId,
+    );
   }
 
   get address(): string {
@@ -67,30 +98,109 @@ export class SolanaSettlementClient {
     return pda;
   }
 
+  private vaultPda(gameId: number): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), gameIdBuf(gameId)],
+      this.programId,
+    );
+    return pda;
+  }
+
+  private treasuryVaultPda(mint: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), mint.toBuffer()],
+      this.programId,
+    );
+    return pda;
+  }
+
+  private lobbyPda(page: number): PublicKey {
+    const b = Buffer.alloc(4);
+    b.writeUInt32LE(page);
+    const [pda] = PublicKey.findProgramAddressSync([Buffer.from("lobby"), b], this.programId);
+    return pda;
+  }
+
+  private clawPda(gameId: number, player: PublicKey): PublicKey {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claw"), gameIdBuf(gameId), player.toBuffer()],
+      this.programId,
+    );
+    return pda;
+  }
+
+  /**
+   * Read what instructions need beyond the game PDA: status, depositing players (seat order),
+   * lobby page and the CLAW mint snapshotted on the game. `clawMint` is null for games created
+   * before CLAW was set up.
+   */
+  private async getGameAccounts(gameId: number): Promise<{
+    status: number;
+    depositors: PublicKey[];
+    lobbyPage: number;
+    clawMint: PublicKey | null;
+    clawTokenProgram: PublicKey | null;
+    commitScheme: number;
+  }> {
+    const info = await this.connection.getAccountInfo(this.gamePda(gameId));
+    if (!info) throw new Error(`Game ${gameId} not found`);
+
+    const d = info.data;
+    const status = d[8 + 8];
+    let off = 8 + 8 + 1; // discriminator, game_id, status
+    const numSeats = d.readUInt32LE(off); off += 4;
+    const players: PublicKey[] = [];
+    for (let i = 0; i < numSeats; i++) { players.push(new PublicKey(d.subarray(off, off + 32))); off += 32; }
+    off += 4;
+    const depositors = players.filter((_, i) => d.subarray(off + i * 32, off + i * 32 + 32).some(b => b !== 0));
+    off += numSeats * 32; // commit_hashes
+    off += 4 + numSeats * 32; // revealed_secrets
+    off += 1 + 1 + 32 + 32 + 32 + 8 + 8 + 8; // counts, dice_seed, winner, log hash, timestamps
+    off += 4 + numSeats; // paid
+    off += 1 + 8 + 1 + 1 + 32 + 8 + 8 + 2; // bump .. platform_fee_bps
+    off += 4 + d.readUInt32LE(off) * 2; // payout_bps
+    off += 4 + d.readUInt32LE(off) * 32; // ranking
+    off += 1 + 32; // platform_paid, mint
+
+    const clawMint = new PublicKey(d.subarray(off, off + 32)); off += 32;
+    const lobbyPage = d.readUInt32LE(off); off += 4;
+    off += 32 + 1 + 8 + 32 + 1; // rent_payer .. version
+    off += 2 + 1 + 8; // forfeit_treasury_bps, forfeited_count, forfeit_share
+    // Games from before commit schemes were versioned end earlier and use scheme 0
+    const commitScheme = off < d.length ? d[off] : 0;
+    if (clawMint.equals(PublicKey.default)) {
+      return { status, depositors, lobbyPage, clawMint: null, clawTokenProgram: null, commitScheme };
+    }
+    const mintInfo = await this.connection.getAccountInfo(clawMint);
+    if (!mintInfo) throw new Error("CLAW mint not found");
+    return { status, depositors, lobbyPage, clawMint, clawTokenProgram: mintInfo.owner, commitScheme };
+  }
+
   private async sendIx(ix: TransactionInstruction): Promise<string> {
     const tx = new Transaction().add(ix);
     return sendAndConfirmTransaction(this.connection, tx, [this.keypair]);
   }
 
-  generateSecret(): { secret: Uint8Array; secretHash: Uint8Array } {
+  /** The commit hash depends on the game, so it is computed in `depositAndCommit`. */
+  generateSecret(): { secret: Uint8Array } {
     this.secret = crypto.randomBytes(32);
-    const secretHash = crypto.createHash("sha3-256").update(this.secret).digest();
-    return { secret: this.secret, secretHash: new Uint8Array(secretHash) };
+    return { secret: this.secret };
   }
 
   /**
-   * Deposit 0.01 SOL + commit secret hash.
+   * Deposit the game's entry fee + commit secret hash.
    * Generates a secret automatically if not already done.
    */
-  async depositAndCommit(gameId: number): Promise<string> {
+  async depositAndCommit(gameId: number, token?: TokenGameAccounts): Promise<string> {
     if (!this.secret) this.generateSecret();
 
-    const secretHash = new Uint8Array(
-      crypto.createHash("sha3-256").update(this.secret!).digest()
-    );
-
     const gamePda = this.gamePda(gameId);
     const disc = anchorDisc("global", "deposit_and_commit");
+    const { status, lobbyPage, clawMint, clawTokenProgram, commitScheme } =
+      await this.getGameAccounts(gameId);
+    const secretHash = commitmentHash(gameId, this.keypair.publicKey, this.secret!, commitScheme);
+    // Open games are listed in a lobby page; fixed-roster games pass the program id (`None`)
+    const lobby = status === 1 ? this.lobbyPda(lobbyPage) : this.programId;
 
     const data = Buffer.alloc(8 + 32);
     disc.copy(data, 0);
@@ -101,15 +211,78 @@ export class SolanaSettlementClient {
       keys: [
         { pubkey: gamePda, isSigner: false, isWritable: true },
         { pubkey: this.platformPda, isSigner: false, isWritable: true },
+        { pubkey: lobby, isSigner: false, isWritable: status === 1 },
         { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       ],
       data,
     });
+    if (token) {
+      ix.keys.push(
+        { pubkey: token.tokenAccount, isSigner: false, isWritable: true },
+        { pubkey: this.vaultPda(gameId), isSigner: false, isWritable: true },
+        { pubkey: token.mint, isSigner: false, isWritable: false },
+        { pubkey: token.tokenProgram, isSigner: false, isWritable: false },
+      );
+    }
+
+    // Games with CLAW open this agent's CLAW account on deposit
+    if (clawMint && clawTokenProgram) {
+      if (!token) {
+        // Unused token-game slots are passed as the program id (Anchor's `None`)
+        for (const writable of [true, true, false, false]) {
+          ix.keys.push({ pubkey: this.programId, isSigner: false, isWritable: writable });
+        }
+      }
+      ix.keys.push(
+        { pubkey: clawMint, isSigner: false, isWritable: false },
+        { pubkey: this.clawPda(gameId, this.keypair.publicKey), isSigner: false, isWritable: true },
+        { pubkey: clawTokenProgram, isSigner: false, isWritable: false },
+      );
+    }
 
     return this.sendIx(ix);
   }
 
+  /** Leave an open game before it fills, taking the entry fee back. The secret is dropped. */
+  async leaveGame(gameId: number, token?: TokenGameAccounts): Promise<string> {
+    const { clawMint, clawTokenProgram } = await this.getGameAccounts(gameId);
+
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.gamePda(gameId), isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
+      ],
+      data: anchorDisc("global", "leave_game"),
+    });
+    if (token) {
+      ix.keys.push(
+        { pubkey: token.tokenAccount, isSigner: false, isWritable: true },
+        { pubkey: this.vaultPda(gameId), isSigner: false, isWritable: true },
+        { pubkey: token.mint, isSigner: false, isWritable: false },
+        { pubkey: token.tokenProgram, isSigner: false, isWritable: false },
+      );
+    }
+
+    // The CLAW account opened on deposit is closed again
+    if (clawMint && clawTokenProgram) {
+      if (!token) {
+        for (const writable of [true, true, false, false]) {
+          ix.keys.push({ pubkey: this.programId, isSigner: false, isWritable: writable });
+        }
+      }
+      ix.keys.push(
+        { pubkey: this.clawPda(gameId, this.keypair.publicKey), isSigner: false, isWritable: true },
+        { pubkey: clawTokenProgram, isSigner: false, isWritable: false },
+      );
+    }
+
+    const sig = await this.sendIx(ix);
+    this.secret = null;
+    return sig;
+  }
+
   async revealSeed(gameId: number): Promise<string> {
     if (!this.secret) throw new Error("No secret to reveal. Call generateSecret() first.");
 
@@ -129,31 +302,142 @@ export class SolanaSettlementClient {
       data,
     });
 
+    // The final reveal starts the game and mints starting CLAW to every depositor
+    const { depositors, clawMint, clawTokenProgram } = await this.getGameAccounts(gameId);
+    if (clawMint && clawTokenProgram) {
+      ix.keys.push(
+        { pubkey: clawMint, isSigner: false, isWritable: true },
+        { pubkey: clawTokenProgram, isSigner: false, isWritable: false },
+        ...depositors.map(p => ({ pubkey: this.clawPda(gameId, p), isSigner: false, isWritable: true })),
+      );
+    }
+
     return this.sendIx(ix);
   }
 
-  async withdraw(gameId: number): Promise<string> {
-    const gamePda = this.gamePda(gameId);
-    const disc = anchorDisc("global", "withdraw");
+  /** Burn this agent's in-game CLAW after settlement or void and recover the account rent. */
+  async reclaimClaw(gameId: number): Promise<string> {
+    const { clawMint, clawTokenProgram } = await this.getGameAccounts(gameId);
+    if (!clawMint || !clawTokenProgram) throw new Error(`Game ${gameId} has no CLAW`);
+
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.gamePda(gameId), isSigner: false, isWritable: true },
+        { pubkey: clawMint, isSigner: false, isWritable: true },
+        { pubkey: this.clawPda(gameId, this.keypair.publicKey), isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+        { pubkey: clawTokenProgram, isSigner: false, isWritable: false },
+      ],
+      data: anchorDisc("global", "reclaim_claw"),
+    });
+
+    return this.sendIx(ix);
+  }
+
+  /** Claim this agent's payout. The first claim also sweeps the platform's rake into the treasury. */
+  async withdraw(gameId: number, token?: TokenGameAccounts): Promise<string> {
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.gamePda(gameId), isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
+        { pubkey: this.treasuryPda, isSigner: false, isWritable: true },
+      ],
+      data: anchorDisc("global", "withdraw"),
+    });
+    if (token) {
+      ix.keys.push(
+        { pubkey: token.tokenAccount, isSigner: false, isWritable: true },
+        { pubkey: this.treasuryVaultPda(token.mint), isSigner: false, isWritable: true },
+        { pubkey: this.vaultPda(gameId), isSigner: false, isWritable: true },
+        { pubkey: token.mint, isSigner: false, isWritable: false },
+        { pubkey: token.tokenProgram, isSigner: false, isWritable: false },
+      );
+    }
 
-    const platformInfo = await this.connection.getAccountInfo(this.platformPda);
-    if (!platformInfo) throw new Error("Platform not initialized");
-    const platformFeeAddr = new PublicKey(platformInfo.data.subarray(8 + 32 + 32, 8 + 32 + 32 + 32));
+    return this.sendIx(ix);
+  }
 
+  /** Take this agent's entry fee back from a voided or cancelled game. */
+  async claimRefund(gameId: number, token?: TokenGameAccounts): Promise<string> {
     const ix = new TransactionInstruction({
       programId: this.programId,
       keys: [
-        { pubkey: gamePda, isSigner: false, isWritable: true },
+        { pubkey: this.gamePda(gameId), isSigner: false, isWritable: true },
         { pubkey: this.keypair.publicKey, isSigner: true, isWritable: true },
-        { pubkey: platformFeeAddr, isSigner: false, isWritable: true },
-        { pubkey: this.platformPda, isSigner: false, isWritable: false },
       ],
-      data: disc,
+      data: anchorDisc("global", "claim_refund"),
     });
+    if (token) {
+      ix.keys.push(
+        { pubkey: token.tokenAccount, isSigner: false, isWritable: true },
+        { pubkey: this.vaultPda(gameId), isSigner: false, isWritable: true },
+        { pubkey: token.mint, isSigner: false, isWritable: false },
+        { pubkey: token.tokenProgram, isSigner: false, isWritable: false },
+      );
+    }
 
     return this.sendIx(ix);
   }
 
+  /** Dispute the GM's result during the challenge period; payouts freeze until it is resolved. */
+  async disputeSettlement(gameId: number): Promise<string> {
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [
+        { pubkey: this.gamePda(gameId), isSigner: false, isWritable: true },
+        { pubkey: this.keypair.publicKey, isSigner: true, isWritable: false },
+      ],
+      data: anchorDisc("global", "dispute_settlement"),
+    });
+
+    return this.sendIx(ix);
+  }
+
+  // ========== DICE AUDIT ==========
+
+  /** Simulate a read-only instruction on the game. No fee is paid. */
+  private async simulateView(gameId: number, data: Buffer): Promise<SimulatedTransactionResponse> {
+    const ix = new TransactionInstruction({
+      programId: this.programId,
+      keys: [{ pubkey: this.gamePda(gameId), isSigner: false, isWritable: false }],
+      data,
+    });
+    const tx = new Transaction().add(ix);
+    tx.feePayer = this.keypair.publicKey;
+    tx.recentBlockhash = (await this.connection.getLatestBlockhash()).blockhash;
+
+    return (await this.connection.simulateTransaction(tx)).value;
+  }
+
+  /** Dice for `turn` as the program derives them from the game's dice seed. */
+  async deriveDice(gameId: number, turn: number): Promise<{ d1: number; d2: number }> {
+    const data = Buffer.alloc(8 + 8);
+    anchorDisc("global", "derive_dice").copy(data, 0);
+    data.writeBigUInt64LE(BigInt(turn), 8);
+    const sim = await this.simulateView(gameId, data);
+    if (sim.err || !sim.returnData) throw new Error(`derive_dice failed: ${JSON.stringify(sim.err)}`);
+    const ret = Buffer.from(sim.returnData.data[0], "base64");
+    return { d1: ret[0], d2: ret[1] };
+  }
+
+  /** True if `rolls` are the dice for consecutive turns from `firstTurn`, checked by the program. */
+  async verifyRolls(
+    gameId: number,
+    firstTurn: number,
+    rolls: { d1: number; d2: number }[],
+  ): Promise<boolean> {
+    const data = Buffer.alloc(8 + 8 + 4 + rolls.length * 2);
+    anchorDisc("global", "verify_rolls").copy(data, 0);
+    data.writeBigUInt64LE(BigInt(firstTurn), 8);
+    data.writeUInt32LE(rolls.length, 16);
+    rolls.forEach((r, i) => { data[20 + i * 2] = r.d1; data[21 + i * 2] = r.d2; });
+    const sim = await this.simulateView(gameId, data);
+    return sim.err === null;
+  }
+
   async getGame(gameId: number): Promise<{
     players: string[];
     status: number;
@@ -173,11 +457,12 @@ export class SolanaSettlementClient {
     off += 8; // game_id
     const status = d[off]; off += 1;
 
+    const numSeats = d.readUInt32LE(off); off += 4;
     const players: string[] = [];
-    for (let i = 0; i < 4; i++) { players.push(readPubkey(d, off)); off += 32; }
+    for (let i = 0; i < numSeats; i++) { players.push(readPubkey(d, off)); off += 32; }
 
-    off += 4 * 32; // commit_hashes
-    off += 4 * 32; // revealed_secrets
+    off += 4 + numSeats * 32; // commit_hashes
+    off += 4 + numSeats * 32; // revealed_secrets
 
     const depositCount = d[off]; off += 1;
     const revealCount = d[off]; off += 1;
@@ -191,7 +476,9 @@ export class SolanaSettlementClient {
     const revealDeadline = d.readBigInt64LE(off); off += 8;
     off += 8; off += 8;
 
-    const winnerPaid = d[off] !== 0;
+    const numPaid = d.readUInt32LE(off); off += 4;
+    const winnerSeat = players.indexOf(winner);
+    const winnerPaid = winnerSeat >= 0 && winnerSeat < numPaid && d[off + winnerSeat] !== 0;
 
     return {
       players: players.map(p => p === ZERO_KEY.toBase58() ? "" : p),
@@ -1,4 +1,4 @@
 export { OpenClawAgent, AgentPolicy, AgentConfig, BnbAgentConfig, SolanaAgentConfig, UnifiedAgentConfig, GameStatusFromGM, GameStateFromGM } from "./OpenClawAgent";
 export { SettlementClient } from "./SettlementClient";
-export { SolanaSettlementClient } from "./SolanaSettlementClient";
+export { SolanaSettlementClient, commitmentHash } from "./SolanaSettlementClient";
 export { AggressivePolicy, ConservativePolicy, SmartPolicy } from "./policies";